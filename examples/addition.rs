@@ -1,6 +1,7 @@
 use tnt::{Deduction, LogicError, Term};
 
-fn main() -> Result<(), LogicError> {
+// The Deduction stored in addition.tnt.
+pub fn deduction() -> Result<Deduction, LogicError> {
     let a = "a";
     let b = "b";
     let zero = Term::zero();
@@ -14,7 +15,11 @@ fn main() -> Result<(), LogicError> {
     d.specification(3, a, &one)?;
     d.successor(4)?;
     d.transitivity(2, 5)?;
+    Ok(d)
+}
 
+fn main() -> Result<(), LogicError> {
+    let d = deduction()?;
     println!("{}", d);
     println!("{}", d.pretty_string());
     println!("{}", d.english_annotated());
@@ -2,7 +2,8 @@ use std::convert::TryFrom;
 
 use tnt::{Deduction, Formula, LogicError, Term};
 
-fn main() -> Result<(), LogicError> {
+// The Deduction stored in commutativity.tnt.
+pub fn deduction() -> Result<Deduction, LogicError> {
     let b = &Term::var("b");
     let c = &Term::var("c");
     let d = &Term::var("d");
@@ -74,7 +75,12 @@ fn main() -> Result<(), LogicError> {
     e.generalization(52, "c")?;
     e.induction("d", 53, 39)?;
 
-    assert_eq!(e.last_theorem().formula, *t);
+    assert_eq!(e.last_theorem().formula(), t);
+    Ok(e)
+}
+
+fn main() -> Result<(), LogicError> {
+    let e = deduction()?;
 
     println!("{}", e.pretty_string());
 
@@ -0,0 +1,368 @@
+//! Read derivations written out as in Gödel, Escher, Bach. Each line holds an optional number such as `(3)` or `3)`, a Formula and an annotation naming the rule that produced it.
+//! ```text
+//! (1) Aa:Ab:(a+Sb)=S(a+b)     axiom 3
+//! (2) Ab:(S0+Sb)=S(S0+b)      specification (S0 for a)
+//! [                           push
+//! (3) a=0                     premise
+//! (4) 0=a                     symmetry
+//! ]                           pop
+//! (5) <a=0⊃0=a>               fantasy rule
+//! ```
+//! The arguments of a rule are never read from the annotation. Instead the rule is applied to whichever accessible theorems produce the Formula, trying each in turn when there are several, so annotations copied from the book only need to name the rule and can be left off entirely. Axioms are numbered from 1 as in the book and the number, if given, is checked.
+//!
+//! Fantasies are opened by a line holding `[`, `push` or `begin supposition` and closed by `]`, `pop` or `end supposition`. The line after an opening is the premise and the line after a closing is the implication it produces. This means the output of Display for a Deduction can also be read back.
+//!
+//! Formulas may use the notation of the book, with angle brackets and `⊃`, or the Unicode symbols of `pretty_string`, as well as the usual ASCII form.
+
+use std::convert::TryFrom;
+
+use crate::check::{underived, INFERRED_RULES};
+use crate::deduction::{Justification, Rule};
+use crate::{Deduction, Formula, LogicError};
+
+fn annotated_error(line: usize, message: &str) -> LogicError {
+    LogicError(format!("Annotation Error: line {line}: {message}"))
+}
+
+// Rewrite a Formula written with the symbols of the book or of pretty_string in ASCII and remove spaces.
+fn normalize(text: &str) -> String {
+    let angle_brackets = text.contains('<') || text.contains('⊃');
+    text.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '∀' => 'A',
+            '∃' => 'E',
+            '¬' => '~',
+            '∧' => '&',
+            '∨' => '|',
+            '⊃' | '⇒' | '→' => '>',
+            '×' | '·' | '⋅' => '*',
+            '<' if angle_brackets => '[',
+            '>' if angle_brackets => ']',
+            c => c,
+        })
+        .collect()
+}
+
+// Split the words of a line into the longest leading Formula and the annotation that follows it. The parser accepts any text that begins with a Formula so the words must be exactly the Formula.
+fn split_formula(words: &[&str]) -> Option<(Formula, String)> {
+    (1..=words.len()).rev().find_map(|k| {
+        let text = normalize(&words[..k].join(" "));
+        Formula::try_from(text.as_str())
+            .ok()
+            .filter(|f| f.to_string() == text)
+            .map(|f| (f, words[k..].join(" ")))
+    })
+}
+
+// The rules an annotation could name. The first word or pair of words of the annotation that names a rule decides, so "specification of axiom 1" is a specification. An annotation that names no rule allows any of them.
+fn hinted_rules(annotation: &str) -> Vec<Rule> {
+    let annotation = annotation.to_lowercase();
+    let words: Vec<&str> = annotation
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let names: &[(&[&str], &[Rule])] = &[
+        (&["axiom"], &[Rule::Axiom]),
+        (&["specification"], &[Rule::Specification]),
+        (&["generalization"], &[Rule::Generalization]),
+        (&["existence"], &[Rule::Existence]),
+        (
+            &["interchange"],
+            &[Rule::InterchangeAE, Rule::InterchangeEA],
+        ),
+        (&["symmetry"], &[Rule::Symmetry]),
+        (&["transitivity"], &[Rule::Transitivity]),
+        (&["add", "s"], &[Rule::Successor]),
+        (&["successor"], &[Rule::Successor]),
+        (&["drop", "s"], &[Rule::Predecessor]),
+        (&["predecessor"], &[Rule::Predecessor]),
+        (&["induction"], &[Rule::Induction]),
+        (&["contrapositive"], &[Rule::Contrapositive]),
+        (&["detachment"], &[Rule::Detachment]),
+        (&["substitution"], &[Rule::Substitution]),
+    ];
+    (0..words.len())
+        .find_map(|i| {
+            names
+                .iter()
+                .find(|(name, _)| words[i..].starts_with(name))
+                .map(|(_, rules)| rules.to_vec())
+        })
+        .unwrap_or_else(|| INFERRED_RULES.to_vec())
+}
+
+// The number of the axiom named by an annotation such as "axiom 2".
+fn axiom_number(annotation: &str) -> Option<usize> {
+    let annotation = annotation.to_lowercase();
+    let mut words = annotation.split_whitespace();
+    words.find(|w| *w == "axiom")?;
+    words.next()?.parse().ok()
+}
+
+// The number at the start of a line written as `(3)` or `3)`.
+fn line_number(word: &str) -> Option<usize> {
+    word.trim_start_matches('(').strip_suffix(')')?.parse().ok()
+}
+
+#[derive(PartialEq)]
+enum Pending {
+    Nothing,
+    Premise,
+    Implication,
+}
+
+// A line holding a Formula together with how it is to be added.
+struct Entry {
+    line_num: usize,
+    kind: Pending,
+    formula: Formula,
+    annotation: String,
+}
+
+// Check the axiom number given in an annotation, if any, against the axiom the line was derived from.
+fn check_axiom_number(d: &Deduction, entry: &Entry) -> Result<(), LogicError> {
+    if let (Some(n), Justification::Axiom(k)) = (
+        axiom_number(&entry.annotation),
+        &d.last_theorem().justification,
+    ) {
+        if n != k + 1 {
+            return Err(annotated_error(
+                entry.line_num,
+                &format!(
+                    "`{}` is axiom {} rather than axiom {n}",
+                    entry.formula,
+                    k + 1
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Add the entries in order, each checked as it is added. A line given without a fantasy marker before it is derived from the accessible theorems by each of the hinted rules in turn. Every way of deriving it gives the same Formula in the same scope and nothing later depends on which way was used, so once a line is added it is never returned to.
+fn rebuild(mut d: Deduction, entries: &[Entry]) -> Result<Deduction, LogicError> {
+    for entry in entries {
+        let formula = &entry.formula;
+        let error = |e: LogicError| annotated_error(entry.line_num, &e.0);
+        match entry.kind {
+            Pending::Premise => d.supposition(formula.clone()).map_err(error)?,
+            Pending::Implication => {
+                d.implication().map_err(error)?;
+                if d.last_theorem().formula() != formula {
+                    return Err(error(LogicError(format!(
+                        "the fantasy gives `{}` rather than `{formula}`",
+                        d.last_theorem().formula()
+                    ))));
+                }
+            }
+            Pending::Nothing => {
+                let rules = hinted_rules(&entry.annotation);
+                let checkpoint = d.checkpoint();
+                let mut first_err = None;
+                // The candidates of a rule are only found once the rules before it have failed
+                let added = rules.iter().any(|rule| {
+                    d.candidates(formula, *rule).iter().any(|j| {
+                        let result = d
+                            .apply(j, Some(formula))
+                            .map_err(error)
+                            .and_then(|_| check_axiom_number(&d, entry));
+                        if let Err(e) = result {
+                            first_err.get_or_insert(e);
+                            d.restore(&checkpoint);
+                            return false;
+                        }
+                        true
+                    })
+                });
+                if !added {
+                    return Err(first_err.unwrap_or_else(|| error(underived(formula, &rules))));
+                }
+            }
+        }
+    }
+    Ok(d)
+}
+
+impl Deduction {
+    /// Reconstruct a Deduction from a derivation annotated in the style of Gödel, Escher, Bach as described in the module documentation. Every line is checked as it is added.
+    /// ```
+    /// # use tnt::Deduction;
+    /// let text = "
+    ///     (1) Aa:(a+0)=a      axiom 2
+    ///     (2) (S0+0)=S0       specification
+    ///     (3) S(S0+0)=SS0     add S
+    /// ";
+    /// let d = Deduction::from_annotated("Annotated", text).unwrap();
+    /// assert_eq!(d.last_theorem().formula().to_string(), "S(S0+0)=SS0");
+    /// ```
+    pub fn from_annotated(title: &str, text: &str) -> Result<Deduction, LogicError> {
+        let mut entries = Vec::new();
+        let mut depth = 0;
+        let mut pending = Pending::Nothing;
+        let mut prev_number: Option<usize> = None;
+        for (i, line) in text.lines().enumerate() {
+            let line_num = i + 1;
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let first = match words.first() {
+                Some(w) => w.to_lowercase(),
+                None => continue,
+            };
+            let phrase = words.join(" ").to_lowercase();
+
+            let opens = first == "[" || first == "push" || phrase == "begin supposition";
+            let closes = first == "]" || first == "pop" || phrase == "end supposition";
+            if opens || closes {
+                if pending != Pending::Nothing {
+                    return Err(annotated_error(line_num, "expected a Formula"));
+                }
+                if closes && depth == 0 {
+                    return Err(annotated_error(line_num, "there is no fantasy to end"));
+                }
+                pending = if opens {
+                    depth += 1;
+                    Pending::Premise
+                } else {
+                    depth -= 1;
+                    Pending::Implication
+                };
+                continue;
+            }
+
+            let mut words = words.as_slice();
+            if let Some(n) = line_number(words[0]) {
+                if let Some(prev) = prev_number {
+                    if n != prev + 1 {
+                        return Err(annotated_error(
+                            line_num,
+                            &format!("line ({n}) follows line ({prev})"),
+                        ));
+                    }
+                }
+                prev_number = Some(n);
+                words = &words[1..];
+            }
+            let (formula, annotation) = split_formula(words)
+                .ok_or_else(|| annotated_error(line_num, "there is no Formula"))?;
+            entries.push(Entry {
+                line_num,
+                kind: std::mem::replace(&mut pending, Pending::Nothing),
+                formula,
+                annotation,
+            });
+        }
+        if pending != Pending::Nothing {
+            return Err(annotated_error(
+                text.lines().count(),
+                "the derivation ends without a Formula",
+            ));
+        }
+        rebuild(Deduction::new(title), &entries)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{check, lemmas};
+
+    #[test]
+    fn test_annotated() -> Result<(), LogicError> {
+        let text = "
+            (1) Aa:Ab:(a+Sb)=S(a+b)     axiom 3
+            (2) Ab:(S0+Sb)=S(S0+b)      specification (S0 for a)
+            (3) (S0+S0)=S(S0+0)         specification (0 for b)
+            (4) Aa:(a+0)=a              axiom 2
+            (5) (S0+0)=S0               specification (S0 for a)
+            (6) S(S0+0)=SS0             add S
+            (7) (S0+S0)=SS0             transitivity (lines 3, 6)
+        ";
+        let d = Deduction::from_annotated("One Plus One", text)?;
+        check(&d)?;
+        assert_eq!(
+            d.theorem(6).justification,
+            Justification::Transitivity(2, 5)
+        );
+
+        let text = "
+            [                   push
+            (1) a=0             premise
+            (2) 0=a             symmetry
+            ]                   pop
+            (3) <a=0⊃0=a>       fantasy rule
+            (4) ∀a:<a=0⊃0=a>    generalization
+        ";
+        let d = Deduction::from_annotated("Fantasy", text)?;
+        check(&d)?;
+        assert_eq!(d.last_theorem().formula().to_string(), "Aa:[a=0>0=a]");
+        assert_eq!(d.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_read_back() -> Result<(), LogicError> {
+        for (lemma, _) in [lemmas::addition_commutes()?, lemmas::distributivity()?] {
+            let d = Deduction::from_annotated(&lemma.title, &lemma.to_string())?;
+            check(&d)?;
+            assert_eq!(d.theorems().len(), lemma.theorems().len());
+            for (t1, t2) in d.theorems().zip(lemma.theorems()) {
+                assert_eq!(t1.formula(), t2.formula());
+                assert_eq!((t1.depth, t1.scope), (t2.depth, t2.scope));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_read_back_err() -> Result<(), LogicError> {
+        // Many lines of the lemma follow in more than one way, none of which can make the last line follow
+        let (lemma, _) = lemmas::distributivity()?;
+        let text = format!("{lemma}\n{}) 0=S0", lemma.theorems().len());
+        match Deduction::from_annotated(&lemma.title, &text) {
+            Err(e) => assert!(e.to_string().contains("`0=S0`")),
+            Ok(_) => panic!("`0=S0` does not follow"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hinted_rules() -> Result<(), LogicError> {
+        assert_eq!(
+            hinted_rules("specification of axiom 1"),
+            vec![Rule::Specification]
+        );
+        assert_eq!(
+            hinted_rules("symmetry (not transitivity)"),
+            vec![Rule::Symmetry]
+        );
+        assert_eq!(hinted_rules("add S"), vec![Rule::Successor]);
+        // only whole words name a rule
+        assert_eq!(hinted_rules("axiomatic"), INFERRED_RULES.to_vec());
+        assert_eq!(hinted_rules("address"), INFERRED_RULES.to_vec());
+
+        // the axiom is used twice so the last line can be derived from either
+        let text = "
+            (1) Aa:(a+0)=a      axiom 2
+            (2) Aa:(a+0)=a      axiom 2
+            (3) (0+0)=0         specification of axiom 2
+        ";
+        let d = Deduction::from_annotated("Twice", text)?;
+        check(&d)?;
+        assert_eq!(d.last_theorem().rule(), Rule::Specification);
+        Ok(())
+    }
+
+    #[test]
+    fn test_annotated_err() {
+        // axiom 2 is Aa:(a+0)=a
+        assert!(Deduction::from_annotated("", "(1) Aa:~Sa=0 axiom 2").is_err());
+        assert!(Deduction::from_annotated("", "(1) Aa:(a+0)=a axiom\n(3) (0+0)=0").is_err());
+        assert!(Deduction::from_annotated("", "(1) 0=0").is_err());
+        assert!(
+            Deduction::from_annotated("", "(1) Aa:(a+0)=a axiom\n(2) (0+0)=0 symmetry").is_err()
+        );
+        assert!(Deduction::from_annotated("", "(1) a=0 premise\n[ push").is_err());
+        assert!(Deduction::from_annotated("", "] pop").is_err());
+    }
+}
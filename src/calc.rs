@@ -0,0 +1,172 @@
+//! Build a chain of equalities from existing theorems. Each step names a theorem that rewrites the current Term and the symmetry and transitivity steps needed to join them are pushed automatically. If any step fails every line pushed by the chain is removed.
+
+use crate::deduction::Checkpoint;
+use crate::{Deduction, Formula, LogicError, Term};
+
+/// An equational chain beginning at some Term. Created by Deduction::calc.
+pub struct Calc<'a> {
+    deduction: &'a mut Deduction,
+    start: Term,
+    current: Term,
+    chain: Option<usize>,
+    checkpoint: Checkpoint,
+}
+
+impl<'a> Calc<'a> {
+    // Extend the chain with theorem n, which must state current=t, and make t the current Term.
+    fn extend(&mut self, n: usize) -> Result<(), LogicError> {
+        self.chain = match self.chain {
+            Some(pos) => {
+                self.deduction.transitivity(pos, n)?;
+                Some(self.deduction.last_theorem().position)
+            }
+            None => Some(n),
+        };
+        Ok(())
+    }
+
+    fn sides(&self, n: usize) -> Result<(Term, Term), LogicError> {
+        match self.deduction.get_theorem(n)? {
+            Formula::Equality(l, r) => Ok((l.clone(), r.clone())),
+            f => Err(LogicError(format!(
+                "Calc Error: theorem {n} `{f}` is not a Formula::Equality"
+            ))),
+        }
+    }
+
+    // Rewrite the current Term using theorem n, reversed if needed. Nothing is pushed unless theorem n applies.
+    fn rewrite(&mut self, n: usize, reversed: bool) -> Result<(), LogicError> {
+        let (l, r) = self.sides(n)?;
+        let (from, to, side) = if reversed {
+            (r, l, "right")
+        } else {
+            (l, r, "left")
+        };
+        if from != self.current {
+            return Err(LogicError(format!(
+                "Calc Error: the {side} side of theorem {n} is `{from}` but the current Term is `{}`",
+                self.current
+            )));
+        }
+        let n = if reversed {
+            self.deduction.symmetry(n)?;
+            self.deduction.last_theorem().position
+        } else {
+            n
+        };
+        self.extend(n)?;
+        self.current = to;
+        Ok(())
+    }
+
+    // Remove every line the chain has pushed and return the error.
+    fn abandon<T>(self, e: LogicError) -> Result<T, LogicError> {
+        self.deduction.restore(&self.checkpoint);
+        Err(e)
+    }
+
+    /// Rewrite the current Term using theorem n, which must be an equality with the current Term on its left side.
+    pub fn step(mut self, n: usize) -> Result<Self, LogicError> {
+        match self.rewrite(n, false) {
+            Ok(()) => Ok(self),
+            Err(e) => self.abandon(e),
+        }
+    }
+
+    /// Rewrite the current Term using theorem n reversed, so theorem n must be an equality with the current Term on its right side.
+    pub fn step_rev(mut self, n: usize) -> Result<Self, LogicError> {
+        match self.rewrite(n, true) {
+            Ok(()) => Ok(self),
+            Err(e) => self.abandon(e),
+        }
+    }
+
+    /// The current Term, which is the right side of the equality proven so far.
+    pub fn current(&self) -> &Term {
+        &self.current
+    }
+
+    /// End the chain and return the position of the theorem stating that the starting Term equals the current Term.
+    pub fn finish(self) -> Result<usize, LogicError> {
+        self.chain.ok_or_else(|| {
+            LogicError(format!(
+                "Calc Error: the chain starting at `{}` has no steps",
+                self.start
+            ))
+        })
+    }
+}
+
+impl Deduction {
+    /// Begin an equational chain at the given Term.
+    /// ```
+    /// # use tnt::{Deduction, Term, LogicError};
+    /// # use std::convert::TryFrom;
+    /// # fn main() -> Result<(), LogicError> {
+    /// let mut d = Deduction::new("Chain");
+    /// let two = &Term::try_from("SS0")?;
+    /// d.add_axiom(1)?; // Aa:(a+0)=a
+    /// d.specification(0, "a", two)?; // (SS0+0)=SS0
+    /// d.add_axiom(3)?; // Aa:(a*0)=0
+    /// d.specification(2, "a", two)?; // (SS0*0)=0
+    /// d.specification(0, "a", &Term::try_from("(SS0*0)")?)?; // ((SS0*0)+0)=(SS0*0)
+    /// let n = d.calc(&Term::try_from("((SS0*0)+0)")?).step(4)?.step(3)?.finish()?;
+    /// assert_eq!(d.theorem(n).formula().to_string(), "((SS0*0)+0)=0");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn calc(&mut self, start: &Term) -> Calc<'_> {
+        Calc {
+            checkpoint: self.checkpoint(),
+            deduction: self,
+            start: start.clone(),
+            current: start.clone(),
+            chain: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_calc() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Chain");
+        let one = &Term::one();
+        d.add_axiom(1)?;
+        d.specification(0, "a", one)?; // (S0+0)=S0
+        d.add_axiom(2)?;
+        d.specification(2, "a", one)?;
+        d.specification(3, "b", &Term::zero())?; // (S0+S0)=S(S0+0)
+        d.successor(1)?; // S(S0+0)=SS0
+        let n = d
+            .calc(&Term::try_from("SS0").unwrap())
+            .step_rev(5)?
+            .step_rev(4)?
+            .finish()?;
+        assert_eq!(d.theorem(n).formula().to_string(), "SS0=(S0+S0)");
+        assert_eq!(n, d.last_theorem().position);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calc_err() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Chain");
+        d.add_axiom(1)?;
+        d.specification(0, "a", &Term::one())?;
+        let zero = &Term::zero();
+        assert!(d.calc(zero).finish().is_err());
+        assert!(d.calc(zero).step(1).is_err());
+        assert!(d.calc(zero).step(0).is_err());
+        assert!(d.calc(&Term::one()).step(1).is_err());
+
+        // a chain that fails partway removes the lines it pushed
+        let len = d.theorems().len();
+        assert!(d.calc(&Term::one()).step_rev(1)?.step(1)?.step(0).is_err());
+        assert_eq!(d.theorems().len(), len);
+        Ok(())
+    }
+}
@@ -0,0 +1,624 @@
+//! Check a Deduction by replaying it. Every line is derived again from its Justification and the axioms of the Deduction, without trusting anything that was recorded when it was built.
+
+use indexmap::IndexSet;
+
+use crate::deduction::{Checkpoint, Justification, Rule, TheoremFrame};
+use crate::{production, Deduction, Formula, LogicError, Term};
+
+// The rules that can be inferred for a line from its Formula alone, in the order they are tried. Suppositions and implications are fixed by the structure of a Deduction instead.
+pub(crate) const INFERRED_RULES: &[Rule] = &[
+    Rule::Axiom,
+    Rule::Specification,
+    Rule::Generalization,
+    Rule::Symmetry,
+    Rule::Transitivity,
+    Rule::Successor,
+    Rule::Predecessor,
+    Rule::Detachment,
+    Rule::Contrapositive,
+    Rule::Induction,
+    Rule::InterchangeAE,
+    Rule::InterchangeEA,
+    Rule::Existence,
+    Rule::Substitution,
+];
+
+// Find the Term that var_name was specified to in pattern, with its universal quantification removed, to give target.
+fn match_formula(
+    pattern: &Formula,
+    var_name: &str,
+    target: &Formula,
+    term: &mut Option<Term>,
+) -> bool {
+    match (pattern, target) {
+        (Formula::Universal(v, inner), _) if v == var_name => {
+            match_formula(inner, var_name, target, term)
+        }
+        (Formula::Equality(a, b), Formula::Equality(c, d)) => {
+            match_term(a, var_name, c, term) && match_term(b, var_name, d, term)
+        }
+        (Formula::Universal(v, p), Formula::Universal(w, t))
+        | (Formula::Existential(v, p), Formula::Existential(w, t)) => {
+            v == w && match_formula(p, var_name, t, term)
+        }
+        (Formula::Negation(p), Formula::Negation(t)) => match_formula(p, var_name, t, term),
+        (Formula::And(p1, p2), Formula::And(t1, t2))
+        | (Formula::Or(p1, p2), Formula::Or(t1, t2))
+        | (Formula::Implies(p1, p2), Formula::Implies(t1, t2)) => {
+            match_formula(p1, var_name, t1, term) && match_formula(p2, var_name, t2, term)
+        }
+        _ => false,
+    }
+}
+
+fn match_term(pattern: &Term, var_name: &str, target: &Term, term: &mut Option<Term>) -> bool {
+    match (pattern, target) {
+        (Term::Variable(v), _) if v == var_name => match term {
+            Some(t) => t == target,
+            None => {
+                *term = Some(target.clone());
+                true
+            }
+        },
+        (Term::Zero, Term::Zero) => true,
+        (Term::Variable(v), Term::Variable(w)) => v == w,
+        (Term::Successor(p), Term::Successor(t)) => match_term(p, var_name, t, term),
+        (Term::Sum(p1, p2), Term::Sum(t1, t2)) | (Term::Product(p1, p2), Term::Product(t1, t2)) => {
+            match_term(p1, var_name, t1, term) && match_term(p2, var_name, t2, term)
+        }
+        _ => false,
+    }
+}
+
+// The error for a Formula that none of the rules derive from the accessible theorems.
+pub(crate) fn underived(formula: &Formula, rules: &[Rule]) -> LogicError {
+    let names: Vec<String> = rules.iter().map(|r| format!("{r:?}")).collect();
+    LogicError(format!(
+        "Check Error: `{formula}` does not follow from the accessible theorems by {}",
+        names.join(", ")
+    ))
+}
+
+impl Deduction {
+    // Apply the rule named by a Justification. A supposition is the only rule that needs its premise to be given, every other rule derives its Formula from earlier lines.
+    pub(crate) fn apply(
+        &mut self,
+        justification: &Justification,
+        premise: Option<&Formula>,
+    ) -> Result<(), LogicError> {
+        let next = self.theorems().len();
+        let check_bounds = |n: &usize| {
+            if *n < next {
+                Ok(())
+            } else {
+                Err(LogicError(format!(
+                    "Check Error: theorem {n} does not come before position {next}"
+                )))
+            }
+        };
+        for n in justification.premises().iter() {
+            check_bounds(n)?;
+        }
+        match justification {
+            Justification::Axiom(n) => self.add_axiom(*n),
+            Justification::Specification {
+                theorem,
+                var_name,
+                term,
+            } => self.specification(*theorem, var_name, term),
+            Justification::Generalization { theorem, var_name } => {
+                self.generalization(*theorem, var_name)
+            }
+            Justification::Existence { theorem, var_name } => self.existence(*theorem, var_name),
+            Justification::Successor(n) => self.successor(*n),
+            Justification::Predecessor(n) => self.predecessor(*n),
+            Justification::InterchangeAE {
+                theorem,
+                var_name,
+                position,
+            } => self.interchange_ae(*theorem, var_name, *position),
+            Justification::InterchangeEA {
+                theorem,
+                var_name,
+                position,
+            } => self.interchange_ea(*theorem, var_name, *position),
+            Justification::Symmetry(n) => self.symmetry(*n),
+            Justification::Transitivity(n1, n2) => self.transitivity(*n1, *n2),
+            Justification::Supposition => match premise {
+                Some(premise) => self.supposition(premise.clone()),
+                None => Err(LogicError(
+                    "Check Error: a supposition needs a premise".to_string(),
+                )),
+            },
+            Justification::Implication { .. } => {
+                if self.depth() == 0 {
+                    return Err(LogicError(
+                        "Check Error: implication outside of a supposition".to_string(),
+                    ));
+                }
+                self.implication()?;
+                // The premise and conclusion are fixed by the open supposition so the recorded ones must agree
+                if &self.last_theorem().justification != justification {
+                    return Err(LogicError(format!(
+                        "Check Error: the supposition ends with `{}` rather than `{justification}`",
+                        self.last_theorem().justification
+                    )));
+                }
+                Ok(())
+            }
+            Justification::Induction {
+                var_name,
+                base,
+                general,
+            } => self.induction(var_name, *base, *general),
+            Justification::Contrapositive(n) => self.contrapositive(*n),
+            Justification::Detachment {
+                premise,
+                implication,
+            } => self.detachment(*premise, *implication),
+            Justification::Substitution {
+                equality,
+                theorem,
+                paths,
+            } => self.substitution(*equality, *theorem, paths),
+        }
+    }
+
+    // Apply the rule named by a Justification and check that it produces the Formula.
+    pub(crate) fn apply_expecting(
+        &mut self,
+        justification: &Justification,
+        formula: &Formula,
+    ) -> Result<(), LogicError> {
+        self.apply(justification, Some(formula))?;
+        let derived = self.last_theorem().formula();
+        if derived != formula {
+            return Err(LogicError(format!(
+                "the {justification} gives `{derived}` rather than `{formula}`"
+            )));
+        }
+        Ok(())
+    }
+
+    // Push the Formula as the next line using the first of the given rules that derives it from an accessible theorem. Every Justification is tried against the theorems from the most recent back.
+    pub(crate) fn infer(&mut self, formula: &Formula, rules: &[Rule]) -> Result<(), LogicError> {
+        for rule in rules {
+            for j in self.candidates(formula, *rule) {
+                if self.apply(&j, Some(formula)).is_ok() {
+                    return Ok(());
+                }
+            }
+        }
+        Err(underived(formula, rules))
+    }
+
+    // Every Justification for the rule whose result is the Formula. The scope restrictions of the rule are not checked.
+    pub(crate) fn candidates(&self, formula: &Formula, rule: Rule) -> Vec<Justification> {
+        let accessible: Vec<(usize, &Formula)> = (0..self.theorems().len())
+            .rev()
+            .filter_map(|n| self.get_theorem(n).ok().map(|f| (n, f)))
+            .collect();
+        let mut out = Vec::new();
+        let unary = |f: fn(&Formula) -> Result<Formula, LogicError>,
+                     j: fn(usize) -> Justification| {
+            accessible
+                .iter()
+                .filter(|(_, p)| f(p).ok().as_ref() == Some(formula))
+                .map(|(n, _)| j(*n))
+                .collect::<Vec<_>>()
+        };
+        match rule {
+            Rule::Axiom => out.extend(
+                self.axioms()
+                    .iter()
+                    .position(|a| a == formula)
+                    .map(Justification::Axiom),
+            ),
+            Rule::Specification => {
+                for (n, p) in accessible.iter() {
+                    let mut bound = IndexSet::new();
+                    p.get_vars_bound(&mut bound);
+                    for v in bound.iter().filter(|v| p.contains_var_bound_universal(v)) {
+                        let mut term = None;
+                        if !match_formula(p, v, formula, &mut term) {
+                            continue;
+                        }
+                        let term = term.unwrap_or_else(|| Term::var(v));
+                        if production::specification(p, v, &term).ok().as_ref() == Some(formula) {
+                            out.push(Justification::Specification {
+                                theorem: *n,
+                                var_name: v.clone(),
+                                term,
+                            });
+                        }
+                    }
+                }
+            }
+            Rule::Generalization | Rule::Existence => {
+                let (v, inner) = match (rule, formula) {
+                    (Rule::Generalization, Formula::Universal(v, inner))
+                    | (Rule::Existence, Formula::Existential(v, inner)) => (v, inner),
+                    _ => return out,
+                };
+                for (n, _) in accessible.iter().filter(|(_, p)| *p == inner.as_ref()) {
+                    let (theorem, var_name) = (*n, v.clone());
+                    out.push(match rule {
+                        Rule::Generalization => Justification::Generalization { theorem, var_name },
+                        _ => Justification::Existence { theorem, var_name },
+                    });
+                }
+            }
+            Rule::Successor => out = unary(production::successor, Justification::Successor),
+            Rule::Predecessor => out = unary(production::predecessor, Justification::Predecessor),
+            Rule::Symmetry => out = unary(production::symmetry, Justification::Symmetry),
+            Rule::Contrapositive => {
+                out = unary(production::contrapositive, Justification::Contrapositive)
+            }
+            Rule::InterchangeAE | Rule::InterchangeEA => {
+                for (n, p) in accessible.iter() {
+                    let mut bound = IndexSet::new();
+                    p.get_vars_bound(&mut bound);
+                    for v in bound.iter() {
+                        let (pattern, f): (_, fn(&Formula, &str, usize) -> _) = match rule {
+                            Rule::InterchangeAE => (format!("A{v}:~"), production::interchange_ae),
+                            _ => (format!("~E{v}:"), production::interchange_ea),
+                        };
+                        let count = p.to_string().matches(&pattern).count();
+                        for position in 0..count {
+                            if f(p, v, position).ok().as_ref() != Some(formula) {
+                                continue;
+                            }
+                            let (theorem, var_name) = (*n, v.clone());
+                            out.push(match rule {
+                                Rule::InterchangeAE => Justification::InterchangeAE {
+                                    theorem,
+                                    var_name,
+                                    position,
+                                },
+                                _ => Justification::InterchangeEA {
+                                    theorem,
+                                    var_name,
+                                    position,
+                                },
+                            });
+                        }
+                    }
+                }
+            }
+            Rule::Transitivity => {
+                if let Formula::Equality(a, c) = formula {
+                    for (n1, p1) in accessible.iter() {
+                        let b = match p1 {
+                            Formula::Equality(l, b) if l == a => b,
+                            _ => continue,
+                        };
+                        for (n2, p2) in accessible.iter() {
+                            if let Formula::Equality(l, r) = p2 {
+                                if l == b && r == c {
+                                    out.push(Justification::Transitivity(*n1, *n2));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Rule::Detachment => {
+                for (implication, p) in accessible.iter() {
+                    let premise = match p {
+                        Formula::Implies(l, r) if r.as_ref() == formula => l,
+                        _ => continue,
+                    };
+                    for (n, _) in accessible.iter().filter(|(_, f)| *f == premise.as_ref()) {
+                        out.push(Justification::Detachment {
+                            premise: *n,
+                            implication: *implication,
+                        });
+                    }
+                }
+            }
+            Rule::Induction => {
+                let v = match formula {
+                    Formula::Universal(v, _) => v,
+                    _ => return out,
+                };
+                let base_case = match production::specification(formula, v, &Term::zero()) {
+                    Ok(f) => f,
+                    Err(_) => return out,
+                };
+                for (base, b) in accessible.iter().filter(|(_, f)| **f == base_case) {
+                    for (general, g) in accessible.iter() {
+                        if production::induction(v, b, g).ok().as_ref() == Some(formula) {
+                            out.push(Justification::Induction {
+                                var_name: v.clone(),
+                                base: *base,
+                                general: *general,
+                            });
+                        }
+                    }
+                }
+            }
+            Rule::Substitution => {
+                for (equality, e) in accessible.iter() {
+                    let (s, t) = match e {
+                        Formula::Equality(s, t) if s != t => (s, t),
+                        _ => continue,
+                    };
+                    for (theorem, p) in accessible.iter() {
+                        let paths: Vec<Vec<usize>> = p
+                            .paths_to(s)
+                            .into_iter()
+                            .filter(|path| formula.term_at(path) == Some(t))
+                            .collect();
+                        if paths.is_empty() {
+                            continue;
+                        }
+                        if production::substitution(e, p, &paths).ok().as_ref() == Some(formula) {
+                            out.push(Justification::Substitution {
+                                equality: *equality,
+                                theorem: *theorem,
+                                paths,
+                            });
+                        }
+                    }
+                }
+            }
+            Rule::Supposition | Rule::Implication => (),
+        }
+        out
+    }
+}
+
+// A line of a Deduction as it was recorded.
+pub(crate) struct Line<'a> {
+    pub position: usize,
+    pub formula: &'a Formula,
+    pub justification: &'a Justification,
+    pub depth: usize,
+    pub scope: usize,
+}
+
+impl<'a> From<&'a TheoremFrame> for Line<'a> {
+    fn from(frame: &'a TheoremFrame) -> Self {
+        Line {
+            position: frame.position,
+            formula: frame.formula(),
+            justification: &frame.justification,
+            depth: frame.depth,
+            scope: frame.scope,
+        }
+    }
+}
+
+// Build a new Deduction from the axioms by applying the Justification of each recorded line in turn, checking that it gives what was recorded.
+pub(crate) fn replay<'a>(
+    title: &str,
+    axioms: Vec<Formula>,
+    lines: impl IntoIterator<Item = Line<'a>>,
+) -> Result<Deduction, LogicError> {
+    let mut replay = Deduction::custom(title, axioms);
+    for (n, line) in lines.into_iter().enumerate() {
+        let invalid =
+            |reason: String| LogicError(format!("Check Error: line {n} is invalid, {reason}"));
+        if line.position != n {
+            return Err(invalid(format!("it is numbered {}", line.position)));
+        }
+        replay
+            .apply_expecting(line.justification, line.formula)
+            .map_err(|e| invalid(e.to_string()))?;
+        let derived = replay.last_theorem();
+        if derived.depth != line.depth || derived.scope != line.scope {
+            return Err(invalid(
+                "its supposition structure does not match".to_string(),
+            ));
+        }
+    }
+    Ok(replay)
+}
+
+// How a line of a Deduction that has only Formulas can have been produced.
+enum Step {
+    Inferred,
+    Implication,
+    Supposition,
+}
+
+// Add the line at position n by the given Step.
+fn take(d: &mut Deduction, n: usize, formula: &Formula, step: Step) -> Result<(), LogicError> {
+    match step {
+        Step::Inferred => d.infer(formula, INFERRED_RULES),
+        Step::Implication => d.implication(),
+        Step::Supposition => d.supposition(formula.clone()),
+    }
+    .map_err(|e| LogicError(format!("Check Error: line {n} is invalid, {e}")))
+}
+
+// A line that could have been produced by more than one Step, with the state of the Deduction before it and the Steps left to try, the last one first.
+struct Choice {
+    position: usize,
+    checkpoint: Checkpoint,
+    untried: Vec<Step>,
+}
+
+// The most times reconstruct returns to an earlier line to try another Step before it gives up.
+const MAX_RETRIES: usize = 1000;
+
+// Why no Deduction was reconstructed from a list of Formulas.
+pub(crate) enum SearchError {
+    // Every reading of the Formulas was tried and none of them is a valid Deduction
+    Invalid(LogicError),
+    // The search gave up before every reading was tried
+    GaveUp(LogicError),
+}
+
+impl From<SearchError> for LogicError {
+    fn from(e: SearchError) -> Self {
+        match e {
+            SearchError::Invalid(e) | SearchError::GaveUp(e) => e,
+        }
+    }
+}
+
+// Build a Deduction from the Formulas of its lines alone, finding a Justification for each and deciding where suppositions begin and end. A line is only taken as a premise if some later line is an implication with that premise. A line that could be produced in more than one way is first taken to follow from earlier lines, which leaves the suppositions as they are, and whenever a later line fails the latest line with another way left is returned to. If no reading works the error is that of the line furthest along that failed, and after MAX_RETRIES returns the search gives up.
+pub(crate) fn reconstruct(
+    title: &str,
+    axioms: Vec<Formula>,
+    formulas: &[Formula],
+) -> Result<Deduction, SearchError> {
+    let premises: Vec<bool> = formulas
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            (i + 1..formulas.len()).any(|j| match &formulas[j] {
+                Formula::Implies(p, c) => p.as_ref() == f && c.as_ref() == &formulas[j - 1],
+                _ => false,
+            })
+        })
+        .collect();
+    let mut d = Deduction::custom(title, axioms);
+    let mut choices: Vec<Choice> = Vec::new();
+    // The error of the line furthest along that failed, which is where the Formulas stop making sense
+    let mut furthest: Option<(usize, LogicError)> = None;
+    let mut retries = 0;
+    let mut n = 0;
+    loop {
+        let mut result = match formulas.get(n) {
+            Some(formula) => {
+                let mut untried = Vec::new();
+                if premises[n] {
+                    untried.push(Step::Supposition);
+                }
+                if d.depth() > 0 {
+                    let premise = d.theorem(d.scopes().1).formula();
+                    if formula == &Formula::implies(premise, d.last_theorem().formula()) {
+                        untried.push(Step::Implication);
+                    }
+                }
+                if !untried.is_empty() {
+                    choices.push(Choice {
+                        position: n,
+                        checkpoint: d.checkpoint(),
+                        untried,
+                    });
+                }
+                take(&mut d, n, formula, Step::Inferred)
+            }
+            None if d.depth() == 0 => return Ok(d),
+            None => Err(LogicError(
+                "Check Error: the Deduction ends inside a supposition".to_string(),
+            )),
+        };
+        while let Err(e) = result {
+            match &furthest {
+                Some((m, _)) if *m >= n => (),
+                _ => furthest = Some((n, e)),
+            }
+            let mut choice = match choices.pop() {
+                Some(choice) => choice,
+                None => {
+                    let (_, e) = furthest.expect("a failed line is recorded");
+                    return Err(SearchError::Invalid(e));
+                }
+            };
+            if choice.position < n {
+                retries += 1;
+                if retries > MAX_RETRIES {
+                    return Err(SearchError::GaveUp(LogicError(format!(
+                        "Check Error: gave up after returning to an earlier line {MAX_RETRIES} times"
+                    ))));
+                }
+            }
+            d.restore(&choice.checkpoint);
+            n = choice.position;
+            let step = choice
+                .untried
+                .pop()
+                .expect("a Choice always has a Step left");
+            if !choice.untried.is_empty() {
+                choices.push(choice);
+            }
+            result = take(&mut d, n, &formulas[n], step);
+        }
+        n += 1;
+    }
+}
+
+/// Replay every line of a Deduction using only its axioms and the Justification of each line. Returns an error naming the first line that cannot be derived or that does not match what was recorded.
+/// ```
+/// # use tnt::{check, Deduction, Term};
+/// let mut d = Deduction::new("Checked");
+/// d.add_axiom(1).unwrap();
+/// d.specification(0, "a", &Term::one()).unwrap();
+/// assert!(check(&d).is_ok());
+/// ```
+pub fn check(deduction: &Deduction) -> Result<(), LogicError> {
+    replay(
+        &deduction.title,
+        deduction.axioms().to_vec(),
+        deduction.theorems().map(Line::from),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::deduction::PEANO_AXIOMS;
+    use crate::{lemmas, Term};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_check() -> Result<(), LogicError> {
+        let (d, _) = lemmas::addition_associates()?;
+        check(&d)?;
+        let mut d = Deduction::new("Suppose");
+        d.supposition(Formula::try_from("a=b").unwrap())?;
+        d.symmetry(0)?;
+        d.implication()?;
+        check(&d)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_reconstruct() -> Result<(), LogicError> {
+        // Each copy of the axiom could be a premise, but the implication only follows if the last one is
+        let axiom = &PEANO_AXIOMS[0];
+        let mut formulas = vec![axiom.clone(); 20];
+        formulas.push(Formula::implies(axiom, axiom));
+        let d = reconstruct("", PEANO_AXIOMS.to_vec(), &formulas)?;
+        assert_eq!(d.theorem(19).justification, Justification::Supposition);
+        assert_eq!(d.depth(), 0);
+
+        // No choice of premises lets the last line follow, and there are too many to try them all
+        formulas.push(Formula::try_from("0=S0").unwrap());
+        match reconstruct("", PEANO_AXIOMS.to_vec(), &formulas) {
+            Err(SearchError::GaveUp(_)) => (),
+            _ => panic!("the search should give up"),
+        }
+        formulas.drain(..14);
+        match reconstruct("", PEANO_AXIOMS.to_vec(), &formulas) {
+            Err(SearchError::Invalid(e)) => assert!(e.to_string().contains("line 7")),
+            _ => panic!("the search should find that `0=S0` does not follow"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_err() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Tampered");
+        d.add_axiom(1)?;
+        d.specification(0, "a", &Term::one())?;
+        d.successor(1)?;
+        d.theorems_mut()[1].justification = Justification::Specification {
+            theorem: 0,
+            var_name: "a".to_string(),
+            term: Term::zero(),
+        };
+        let e = check(&d).unwrap_err();
+        assert!(e.to_string().contains("line 1"));
+        d.theorems_mut()[1].justification = Justification::Successor(2);
+        let e = check(&d).unwrap_err();
+        assert!(e.to_string().contains("line 1"));
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+//! Decide the truth of Formulas in the standard model of arithmetic without constructing a proof. Only closed quantifier-free Formulas and Formulas whose quantifiers are bounded are decidable this way.
+
+use indexmap::IndexSet;
+use num::{BigUint, One, Zero};
+use std::collections::HashMap;
+
+use crate::{Formula, LogicError, Term};
+
+/// Evaluate a Term by looking up the value of each Variable in the environment.
+pub(crate) fn eval_term(
+    term: &Term,
+    env: &HashMap<String, BigUint>,
+) -> Result<BigUint, LogicError> {
+    match term {
+        Term::Zero => Ok(BigUint::zero()),
+        Term::Variable(v) => match env.get(v) {
+            Some(n) => Ok(n.clone()),
+            None => Err(LogicError(format!(
+                "Decision Error: the Term::Variable `{v}` is free and has no value"
+            ))),
+        },
+        Term::Successor(inner) => Ok(eval_term(inner, env)? + BigUint::one()),
+        Term::Sum(lhs, rhs) => Ok(eval_term(lhs, env)? + eval_term(rhs, env)?),
+        Term::Product(lhs, rhs) => Ok(eval_term(lhs, env)? * eval_term(rhs, env)?),
+    }
+}
+
+/// If the Formula has the form Ey:(s+Sy)=t with y not appearing in s or t then it asserts s<t. Return s and t.
+pub(crate) fn less_than(formula: &Formula) -> Option<(&Term, &Term)> {
+    if let Formula::Existential(y, inner) = formula {
+        if let Formula::Equality(Term::Sum(s, sy), t) = &**inner {
+            if **sy == Term::succ(&Term::Variable(y.clone()))
+                && !s.contains_var(y)
+                && !t.contains_var(y)
+            {
+                return Some((s, t));
+            }
+        }
+    }
+    None
+}
+
+/// If the Formula is a bounded quantification of the Variable x, with a guard of the form Ey:(x+Sy)=t, return x, t and the guarded Formula.
+/// The universal form is Ax:[Ey:(x+Sy)=t>P] and the existential form is Ex:[Ey:(x+Sy)=t&P].
+fn bounded(formula: &Formula) -> Option<(&String, &Term, &Formula)> {
+    let (x, inner) = match formula {
+        Formula::Universal(x, inner) | Formula::Existential(x, inner) => (x, inner),
+        _ => return None,
+    };
+    let (guard, body) = match (formula, &**inner) {
+        (Formula::Universal(_, _), Formula::Implies(guard, body)) => (guard, body),
+        (Formula::Existential(_, _), Formula::And(guard, body)) => (guard, body),
+        _ => return None,
+    };
+    match less_than(guard) {
+        Some((Term::Variable(v), t)) if v == x && !t.contains_var(x) => Some((x, t, body)),
+        _ => None,
+    }
+}
+
+fn decide_with(formula: &Formula, env: &mut HashMap<String, BigUint>) -> Result<bool, LogicError> {
+    if let Some((s, t)) = less_than(formula) {
+        return Ok(eval_term(s, env)? < eval_term(t, env)?);
+    }
+    if let Some((x, t, body)) = bounded(formula) {
+        let bound = eval_term(t, env)?;
+        let universal = matches!(formula, Formula::Universal(_, _));
+        // The bound Variable shadows any outer Variable of the same name so the old value must be restored afterward.
+        let shadowed = env.remove(x);
+        let mut out = Ok(universal);
+        let mut n = BigUint::zero();
+        while n < bound {
+            env.insert(x.clone(), n.clone());
+            match decide_with(body, env) {
+                Ok(value) if value == universal => (),
+                other => {
+                    out = other;
+                    break;
+                }
+            }
+            n += BigUint::one();
+        }
+        match shadowed {
+            Some(old) => env.insert(x.clone(), old),
+            None => env.remove(x),
+        };
+        return out;
+    }
+    match formula {
+        Formula::Equality(lhs, rhs) => Ok(eval_term(lhs, env)? == eval_term(rhs, env)?),
+        Formula::Negation(inner) => Ok(!decide_with(inner, env)?),
+        Formula::And(lhs, rhs) => Ok(decide_with(lhs, env)? && decide_with(rhs, env)?),
+        Formula::Or(lhs, rhs) => Ok(decide_with(lhs, env)? || decide_with(rhs, env)?),
+        Formula::Implies(lhs, rhs) => Ok(!decide_with(lhs, env)? || decide_with(rhs, env)?),
+        Formula::Universal(_, _) | Formula::Existential(_, _) => Err(LogicError(format!(
+            "Decision Error: the quantification in `{formula}` is not bounded"
+        ))),
+    }
+}
+
+/// Determine if a Formula is true in the standard model of arithmetic. The Formula must have no free Variables and every quantification must be bounded by a guard asserting the Variable is less than some Term.
+///
+/// Less than is written Ey:(s+Sy)=t. A bounded universal quantification is written Ax:[Ey:(x+Sy)=t>P] and a bounded existential quantification is written Ex:[Ey:(x+Sy)=t&P].
+/// ```
+/// # use tnt::Formula;
+/// # use std::convert::TryFrom;
+/// # use tnt::decide;
+/// let f = &Formula::try_from("Aa:[Eb:(a+Sb)=SSS0>~(a*a)=SS0]").unwrap();
+/// assert!(decide(f).unwrap()); // no square is two
+/// ```
+pub fn decide(formula: &Formula) -> Result<bool, LogicError> {
+    // Evaluation stops as soon as the answer is known so a free Variable might never be reached
+    let mut free = IndexSet::new();
+    formula.get_vars_free(&mut free);
+    if !free.is_empty() {
+        return Err(LogicError(format!(
+            "Decision Error: the Formula `{formula}` contains free Term::Variables"
+        )));
+    }
+    decide_with(formula, &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_quantifier_free() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("(SS0+SS0)=(SS0*SS0)").unwrap();
+        let f2 = &Formula::try_from("[~S0=0>(S0*0)=S0]").unwrap();
+        assert!(decide(f1)?);
+        assert!(!decide(f2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_less_than() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("Eb:(SS0+Sb)=SSS0").unwrap();
+        let f2 = &Formula::try_from("Eb:(SSS0+Sb)=SSS0").unwrap();
+        assert!(decide(f1)?);
+        assert!(!decide(f2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bounded() -> Result<(), LogicError> {
+        // every number below four is even or odd
+        let f1 = &Formula::try_from("Aa:[Eb:(a+Sb)=SSSS0>Ec:[Eb:(c+Sb)=Sa&[(c+c)=a|S(c+c)=a]]]")
+            .unwrap();
+        // no number below three has a square equal to five
+        let f2 = &Formula::try_from("Ea:[Eb:(a+Sb)=SSS0&(a*a)=SSSSS0]").unwrap();
+        // an empty bound makes universal quantification vacuously true
+        let f3 = &Formula::try_from("Aa:[Eb:(a+Sb)=0>a=S0]").unwrap();
+        assert!(decide(f1)?);
+        assert!(!decide(f2)?);
+        assert!(decide(f3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decide_err() {
+        let free = &Formula::try_from("a=0").unwrap();
+        let unbounded = &Formula::try_from("Aa:(a+0)=a").unwrap();
+        assert!(decide(free).is_err());
+        assert!(decide(unbounded).is_err());
+        // free Variables that evaluation would never reach
+        for f in ["[0=0|a=0]", "[S0=0&a=0]", "Aa:[Eb:(a+Sb)=0>c=0]"] {
+            assert!(decide(&Formula::try_from(f).unwrap()).is_err());
+        }
+    }
+}
@@ -1,17 +1,26 @@
 use indexmap::IndexSet;
+use lazy_static::lazy_static;
 use num::BigUint;
-use std::{
-    convert::TryFrom,
-    fmt,
-    fs::File,
-    io::{Error, Write},
-    slice::Iter,
-};
+use std::{convert::TryFrom, fmt, slice::Iter};
 
-use crate::{production::*, Formula, LogicError, Term};
+use crate::check::{reconstruct, SearchError};
+use crate::numbering::{ByteNumbering, GodelNumbering};
+use crate::{Formula, LogicError, Term, Theorem};
+
+lazy_static! {
+    /// The axioms used by Deduction::new.
+    pub static ref PEANO_AXIOMS: Vec<Formula> = vec![
+        Formula::try_from("Aa:~Sa=0").unwrap(),
+        Formula::try_from("Aa:(a+0)=a").unwrap(),
+        Formula::try_from("Aa:Ab:(a+Sb)=S(a+b)").unwrap(),
+        Formula::try_from("Aa:(a*0)=0").unwrap(),
+        Formula::try_from("Aa:Ab:(a*Sb)=((a*b)+a)").unwrap(),
+    ];
+}
 
 /// All the rules of production.
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rule {
     Axiom,
     Specification,
@@ -26,37 +35,320 @@ pub enum Rule {
     Supposition,
     Implication,
     Induction,
+    Contrapositive,
+    Detachment,
+    /// Replacement of a Term by an equal Term inside a Formula. This is an extension of TNT and is not derived from its rules.
+    Substitution,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Axiom => "axiom",
+            Self::Specification => "specification",
+            Self::Generalization => "generalization",
+            Self::Existence => "existence",
+            Self::Successor => "successor",
+            Self::Predecessor => "predecessor",
+            Self::InterchangeAE | Self::InterchangeEA => "interchange",
+            Self::Symmetry => "symmetry",
+            Self::Transitivity => "transitivity",
+            Self::Supposition => "supposition",
+            Self::Implication => "implication",
+            Self::Induction => "induction",
+            Self::Contrapositive => "contrapositive",
+            Self::Detachment => "detachment",
+            Self::Substitution => "substitution",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The rule used to produce a theorem along with the positions of the theorems it was applied to and any other arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Justification {
+    Axiom(usize),
+    Specification {
+        theorem: usize,
+        var_name: String,
+        term: Term,
+    },
+    Generalization {
+        theorem: usize,
+        var_name: String,
+    },
+    Existence {
+        theorem: usize,
+        var_name: String,
+    },
+    Successor(usize),
+    Predecessor(usize),
+    InterchangeAE {
+        theorem: usize,
+        var_name: String,
+        position: usize,
+    },
+    InterchangeEA {
+        theorem: usize,
+        var_name: String,
+        position: usize,
+    },
+    Symmetry(usize),
+    Transitivity(usize, usize),
+    Supposition,
+    Implication {
+        premise: usize,
+        conclusion: usize,
+    },
+    Induction {
+        var_name: String,
+        base: usize,
+        general: usize,
+    },
+    Contrapositive(usize),
+    Detachment {
+        premise: usize,
+        implication: usize,
+    },
+    Substitution {
+        equality: usize,
+        theorem: usize,
+        paths: Vec<Vec<usize>>,
+    },
+}
+
+impl Justification {
+    /// The rule of production that was used.
+    pub fn rule(&self) -> Rule {
+        match self {
+            Self::Axiom(_) => Rule::Axiom,
+            Self::Specification { .. } => Rule::Specification,
+            Self::Generalization { .. } => Rule::Generalization,
+            Self::Existence { .. } => Rule::Existence,
+            Self::Successor(_) => Rule::Successor,
+            Self::Predecessor(_) => Rule::Predecessor,
+            Self::InterchangeAE { .. } => Rule::InterchangeAE,
+            Self::InterchangeEA { .. } => Rule::InterchangeEA,
+            Self::Symmetry(_) => Rule::Symmetry,
+            Self::Transitivity(_, _) => Rule::Transitivity,
+            Self::Supposition => Rule::Supposition,
+            Self::Implication { .. } => Rule::Implication,
+            Self::Induction { .. } => Rule::Induction,
+            Self::Contrapositive(_) => Rule::Contrapositive,
+            Self::Detachment { .. } => Rule::Detachment,
+            Self::Substitution { .. } => Rule::Substitution,
+        }
+    }
+
+    /// The positions of the theorems the rule was applied to, in the order the rule takes them.
+    pub fn premises(&self) -> Vec<usize> {
+        match self {
+            Self::Axiom(_) | Self::Supposition => vec![],
+            Self::Specification { theorem, .. }
+            | Self::Generalization { theorem, .. }
+            | Self::Existence { theorem, .. }
+            | Self::InterchangeAE { theorem, .. }
+            | Self::InterchangeEA { theorem, .. } => vec![*theorem],
+            Self::Successor(n)
+            | Self::Predecessor(n)
+            | Self::Symmetry(n)
+            | Self::Contrapositive(n) => {
+                vec![*n]
+            }
+            Self::Transitivity(n1, n2) => vec![*n1, *n2],
+            Self::Implication {
+                premise,
+                conclusion,
+            } => vec![*premise, *conclusion],
+            Self::Induction { base, general, .. } => vec![*base, *general],
+            Self::Detachment {
+                premise,
+                implication,
+            } => vec![*premise, *implication],
+            Self::Substitution {
+                equality, theorem, ..
+            } => vec![*equality, *theorem],
+        }
+    }
+
+    // Shift every theorem position by the offset, used when splicing in another Deduction.
+    fn offset(&self, offset: usize) -> Justification {
+        let mut out = self.clone();
+        match &mut out {
+            Self::Axiom(_) | Self::Supposition => (),
+            Self::Specification { theorem, .. }
+            | Self::Generalization { theorem, .. }
+            | Self::Existence { theorem, .. }
+            | Self::InterchangeAE { theorem, .. }
+            | Self::InterchangeEA { theorem, .. }
+            | Self::Successor(theorem)
+            | Self::Predecessor(theorem)
+            | Self::Symmetry(theorem)
+            | Self::Contrapositive(theorem) => *theorem += offset,
+            Self::Transitivity(n1, n2)
+            | Self::Implication {
+                premise: n1,
+                conclusion: n2,
+            }
+            | Self::Induction {
+                base: n1,
+                general: n2,
+                ..
+            }
+            | Self::Detachment {
+                premise: n1,
+                implication: n2,
+            }
+            | Self::Substitution {
+                equality: n1,
+                theorem: n2,
+                ..
+            } => {
+                *n1 += offset;
+                *n2 += offset;
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Justification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Axiom(_) => write!(f, "axiom"),
+            Self::Specification {
+                theorem,
+                var_name,
+                term,
+            } => write!(
+                f,
+                "specification of {var_name} to {term} in theorem {theorem}"
+            ),
+            Self::Generalization { theorem, var_name } => {
+                write!(f, "generalization of {var_name} in theorem {theorem}")
+            }
+            Self::Existence { theorem, var_name } => {
+                write!(f, "existence of {var_name} in theorem {theorem}")
+            }
+            Self::Successor(n) => write!(f, "successor of theorem {n}"),
+            Self::Predecessor(n) => write!(f, "predecessor of theorem {n}"),
+            Self::InterchangeAE {
+                theorem, var_name, ..
+            } => write!(
+                f,
+                "interchange A{var_name}:~ for ~E{var_name}: in theorem {theorem}"
+            ),
+            Self::InterchangeEA {
+                theorem, var_name, ..
+            } => write!(
+                f,
+                "interchange ~E{var_name}: for A{var_name}:~ in theorem {theorem}"
+            ),
+            Self::Symmetry(n) => write!(f, "symmetry of theorem {n}"),
+            Self::Transitivity(n1, n2) => {
+                write!(f, "transitivity of theorem {n1} and theorem {n2}")
+            }
+            Self::Supposition => write!(f, "supposition"),
+            Self::Implication {
+                premise,
+                conclusion,
+            } => write!(
+                f,
+                "implication of theorem {premise} and theorem {conclusion}"
+            ),
+            Self::Induction {
+                var_name,
+                base,
+                general,
+            } => write!(
+                f,
+                "induction of {var_name} on theorems {base} and {general}"
+            ),
+            Self::Contrapositive(n) => write!(f, "contrapositive of theorem {n}"),
+            Self::Detachment {
+                premise,
+                implication,
+            } => write!(
+                f,
+                "detachment of theorem {premise} from theorem {implication}"
+            ),
+            Self::Substitution {
+                equality, theorem, ..
+            } => write!(
+                f,
+                "substitution (extension rule) of theorem {equality} into theorem {theorem}"
+            ),
+        }
+    }
 }
 
-/// Information tracked about each Formula
+/// Information tracked about each Theorem
 #[derive(Clone)]
 pub struct TheoremFrame {
-    pub formula: Formula,
-    pub depth: usize,
-    pub position: usize,
-    pub annotation: String,
-    pub rule: Rule,
-    pub scope: usize,
+    theorem: Theorem,
+    pub(crate) depth: usize,
+    pub(crate) position: usize,
+    pub(crate) justification: Justification,
+    pub(crate) scope: usize,
 }
 
 impl TheoremFrame {
-    pub fn new(
-        formula: Formula,
+    pub(crate) fn new(
+        theorem: Theorem,
         depth: usize,
         position: usize,
-        annotation: String,
-        rule: Rule,
+        justification: Justification,
         scope: usize,
     ) -> TheoremFrame {
         TheoremFrame {
-            formula,
+            theorem,
             depth,
             position,
-            annotation,
-            rule,
+            justification,
             scope,
         }
     }
+
+    /// The number of suppositions the theorem is inside of.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The line of the Deduction the theorem is on, counted from 0.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// How the theorem was produced from earlier lines.
+    pub fn justification(&self) -> &Justification {
+        &self.justification
+    }
+
+    /// The position of the supposition the theorem is inside of, or 0 at the top level.
+    pub fn scope(&self) -> usize {
+        self.scope
+    }
+
+    /// The rule of production that produced the theorem.
+    pub fn rule(&self) -> Rule {
+        self.justification.rule()
+    }
+
+    /// A description of how the theorem was produced.
+    pub fn annotation(&self) -> String {
+        self.justification.to_string()
+    }
+
+    /// The Formula that was proven.
+    pub fn formula(&self) -> &Formula {
+        self.theorem.formula()
+    }
+
+    /// The Theorem that certifies the Formula.
+    pub fn theorem(&self) -> &Theorem {
+        &self.theorem
+    }
 }
 
 /// Enforces valid use of deductive logic to produce proofs in Typographical Number Theory and outputs formatted results.
@@ -70,6 +362,25 @@ pub struct Deduction {
     theorems: Vec<TheoremFrame>,
 }
 
+// The state of a Deduction that a failed sequence of steps is rolled back to.
+#[derive(Clone, Debug)]
+pub(crate) struct Checkpoint {
+    index: usize,
+    scope_stack: Vec<usize>,
+    scope_cur: usize,
+}
+
+/// A step in reading a Deduction from its first line to its last, for displays that draw each supposition as a block.
+#[derive(Clone, Copy)]
+pub enum ScopeEvent<'a> {
+    /// A supposition begins, its premise is the next line.
+    Open(&'a TheoremFrame),
+    /// A line of the Deduction.
+    Line(&'a TheoremFrame),
+    /// The innermost supposition ends, returning to the given depth.
+    Close(usize),
+}
+
 // When 'true' forces the theorems to be printed every time they are added, helps with debugging
 const NOISY: bool = false;
 
@@ -98,45 +409,114 @@ impl Deduction {
      *
      * Aa:Ab:(a\*Sb)=((a\*b)+a)  for all a and b, (a × (b + 1)) = ((a × b) + a)
      */
-
     pub fn new(title: &str) -> Deduction {
-        let peano_axioms = vec![
-            Formula::try_from("Aa:~Sa=0").unwrap(),
-            Formula::try_from("Aa:(a+0)=a").unwrap(),
-            Formula::try_from("Aa:Ab:(a+Sb)=S(a+b)").unwrap(),
-            Formula::try_from("Aa:(a*0)=0").unwrap(),
-            Formula::try_from("Aa:Ab:(a*Sb)=((a*b)+a)").unwrap(),
-        ];
-
         Deduction {
             index: 0,
             scope_stack: vec![0],
             scope_cur: 0,
             title: title.to_string(),
-            axioms: peano_axioms,
+            axioms: PEANO_AXIOMS.clone(),
             theorems: Vec::<TheoremFrame>::new(),
         }
     }
 
     // Internal methods
     // Get a theorem if it is in an accessible scope
-    fn get_theorem(&self, n: usize) -> Result<&Formula, LogicError> {
+    pub(crate) fn get_theorem(&self, n: usize) -> Result<&Formula, LogicError> {
         // Check the scope
         let tscope = self.theorems[n].scope;
         if tscope == self.scope_cur || self.scope_stack.contains(&tscope) {
-            return Ok(&self.theorems[n].formula);
+            return Ok(self.theorems[n].formula());
         }
         let msg = format!("Scope Error: position {n} is not in an accessible scope");
         Err(LogicError::new(msg))
     }
 
+    // Record the length and scopes of the Deduction so that lines pushed afterward can be undone.
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            index: self.index,
+            scope_stack: self.scope_stack.clone(),
+            scope_cur: self.scope_cur,
+        }
+    }
+
+    // Remove every line pushed since the checkpoint was made.
+    pub(crate) fn restore(&mut self, checkpoint: &Checkpoint) {
+        self.theorems.truncate(checkpoint.index);
+        self.index = checkpoint.index;
+        self.scope_stack = checkpoint.scope_stack.clone();
+        self.scope_cur = checkpoint.scope_cur;
+    }
+
+    // Make a sequence of steps that either all succeed or leave the Deduction as it was.
+    pub(crate) fn atomic<T>(
+        &mut self,
+        steps: impl FnOnce(&mut Deduction) -> Result<T, LogicError>,
+    ) -> Result<T, LogicError> {
+        let checkpoint = self.checkpoint();
+        let result = steps(self);
+        if result.is_err() {
+            self.restore(&checkpoint);
+        }
+        result
+    }
+
+    // The positions of the enclosing suppositions and of the current one.
+    pub(crate) fn scopes(&self) -> (&[usize], usize) {
+        (&self.scope_stack, self.scope_cur)
+    }
+
+    // Get the Theorem certifying a theorem if it is in an accessible scope
+    fn get_certificate(&self, n: usize) -> Result<&Theorem, LogicError> {
+        self.get_theorem(n)?;
+        Ok(&self.theorems[n].theorem)
+    }
+
+    // The premises of the suppositions that are still open, outermost first. The scope stack begins with the top level and the first supposition pushes the top level again, so neither of the first two entries is a supposition.
+    fn open_suppositions(&self) -> impl Iterator<Item = &Formula> {
+        let open = self.scope_stack.get(2..).unwrap_or(&[]);
+        let current = if self.depth() > 0 {
+            Some(&self.scope_cur)
+        } else {
+            None
+        };
+        open.iter()
+            .chain(current)
+            .map(move |n| self.theorems[*n].formula())
+    }
+
+    // A variable that is free in the premise of any open supposition cannot be universally quantified.
+    fn check_unsupposed(&self, var_name: &str, rule: &str) -> Result<(), LogicError> {
+        for premise in self.open_suppositions() {
+            let mut free_vars = IndexSet::<String>::new();
+            premise.get_vars_free(&mut free_vars);
+            if free_vars.contains(var_name) {
+                return Err(LogicError::new(format!(
+                    "{rule} Error: the variable {var_name} is free in the supposition {premise}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // The names of all variables that appear in the premises of open suppositions.
+    pub(crate) fn supposition_vars(&self) -> IndexSet<String> {
+        let mut vars = IndexSet::new();
+        for premise in self.open_suppositions() {
+            premise.get_vars(&mut vars);
+        }
+        vars
+    }
+
     // The last theorem on the list is always in an accessible scope.
-    fn get_last_theorem(&self) -> &Formula {
-        &self.theorems.last().unwrap().formula
+    fn get_last_theorem(&self) -> &Theorem {
+        &self.theorems.last().unwrap().theorem
     }
 
     // Pushes a new TheoremFrame and updates the index
-    fn push_new(&mut self, theorem: Formula, annotation: String, rule: Rule) {
+    fn push_new(&mut self, theorem: Theorem, justification: Justification) {
+        let rule = justification.rule();
         if NOISY {
             if rule == Rule::Supposition {
                 println!("{}begin supposition", "   ".repeat(self.depth() - 1))
@@ -147,8 +527,8 @@ impl Deduction {
                 "{}{}) {} [{}]",
                 "   ".repeat(self.depth()),
                 self.index,
-                theorem,
-                annotation
+                theorem.formula(),
+                justification
             )
         }
         let depth = match rule {
@@ -156,14 +536,7 @@ impl Deduction {
             Rule::Implication => self.depth() - 1,
             _ => self.depth(),
         };
-        let t = TheoremFrame {
-            formula: theorem,
-            depth: depth,
-            position: self.index,
-            annotation,
-            rule,
-            scope: self.scope_cur,
-        };
+        let t = TheoremFrame::new(theorem, depth, self.index, justification, self.scope_cur);
 
         self.theorems.push(t);
         self.index += 1;
@@ -185,11 +558,38 @@ impl Deduction {
 
     /// Return the last TheoremFrame.
     pub fn last_theorem(&self) -> &TheoremFrame {
-        &self.theorems.last().unwrap()
+        self.theorems.last().unwrap()
+    }
+
+    /// The axioms of the Deduction.
+    pub fn axioms(&self) -> &[Formula] {
+        &self.axioms
+    }
+
+    // Allows tests to tamper with a Deduction.
+    #[cfg(test)]
+    pub(crate) fn theorems_mut(&mut self) -> &mut Vec<TheoremFrame> {
+        &mut self.theorems
+    }
+
+    /// The lines of the Deduction with an event before each supposition and after the last line inside it. Every supposition is closed by the end, including those never discharged.
+    pub fn scope_events(&self) -> impl Iterator<Item = ScopeEvent<'_>> {
+        let mut events = Vec::new();
+        let mut depth = 0;
+        for t in self.theorems.iter() {
+            events.extend((t.depth..depth).rev().map(ScopeEvent::Close));
+            if t.justification == Justification::Supposition {
+                events.push(ScopeEvent::Open(t));
+            }
+            events.push(ScopeEvent::Line(t));
+            depth = t.depth;
+        }
+        events.extend((0..depth).rev().map(ScopeEvent::Close));
+        events.into_iter()
     }
 
     /// Iterate over TheoremFrames of the Deduction.
-    pub fn theorems(&self) -> Iter<TheoremFrame> {
+    pub fn theorems(&self) -> Iter<'_, TheoremFrame> {
         self.theorems.iter()
     }
 
@@ -204,13 +604,12 @@ impl Deduction {
             } else if t.depth < prev_depth {
                 let end = format!("\n{}end supposition", "   ".repeat(t.depth));
                 out.push_str(&end);
-            } else {
             }
             let line = format!(
                 "\n{}{}) {}",
                 "   ".repeat(t.depth),
                 pos,
-                t.formula.pretty_string()
+                t.formula().pretty_string()
             );
             out.push_str(&line);
             prev_depth = t.depth;
@@ -218,57 +617,10 @@ impl Deduction {
         out
     }
 
-    /// Create an annotated LaTeX file the given file name that displays the Deduction.
-    pub fn latex_file(&self, filename: &str) -> Result<(), Error> {
-        let filename = format!("{}.tex", filename);
-        let mut file = File::create(filename)?;
-
-        let section_title = format!("\\section*{{{}}}\n", self.title);
-
-        file.write(b"\\documentclass[fleqn,11pt]{article}\n")?;
-        file.write(b"\\usepackage{amsmath}\n")?;
-        file.write(b"\\allowdisplaybreaks\n")?;
-        file.write(b"\\begin{document}\n")?;
-        file.write(&section_title.into_bytes())?;
-        file.write(b"\\begin{flalign*}\n")?;
-
-        let mut prev_depth = 0;
-        for (pos, t) in self.theorems.iter().enumerate() {
-            if t.depth > prev_depth {
-                let line = format!(
-                    "&{}\\text{{begin supposition}}&\\\\\n",
-                    "   ".repeat(prev_depth)
-                )
-                .into_bytes();
-                file.write(&line)?;
-            } else if t.depth < prev_depth {
-                let line = format!("&{}\\text{{end supposition}}&\\\\\n", "   ".repeat(t.depth))
-                    .into_bytes();
-                file.write(&line)?;
-            }
-
-            let line = format!(
-                "&\\hspace{{{}em}}{})\\hspace{{1em}}{}\\hspace{{2em}}\\textbf{{[{}]}}\\\\\n",
-                t.depth * 2,
-                pos,
-                t.formula.to_latex(),
-                t.annotation
-            )
-            .into_bytes();
-            file.write(&line)?;
-
-            prev_depth = t.depth;
-        }
-
-        file.write(b"\\end{flalign*}\n")?;
-        file.write(b"\\end{document}")?;
-        Ok(())
-    }
-
     pub fn english(&self) -> String {
         let mut out = String::new();
         for t in self.theorems.iter() {
-            out.push_str(&format!("\n{}) {}", t.position, t.formula.to_english()));
+            out.push_str(&format!("\n{}) {}", t.position, t.formula().to_english()));
         }
         out
     }
@@ -279,8 +631,8 @@ impl Deduction {
             out.push_str(&format!(
                 "\n{}) {} [{}]",
                 t.position,
-                t.formula.to_english(),
-                t.annotation
+                t.formula().to_english(),
+                t.annotation()
             ));
         }
         out
@@ -290,7 +642,8 @@ impl Deduction {
     /// Push any axiom of the Deduction system into the theorems.
     pub fn add_axiom(&mut self, premise: usize) -> Result<(), LogicError> {
         if let Some(axiom) = self.axioms.get(premise) {
-            self.push_new(axiom.clone(), "axiom".to_string(), Rule::Axiom);
+            let t = Theorem::axiom(axiom);
+            self.push_new(t, Justification::Axiom(premise));
             Ok(())
         } else {
             Err(LogicError(format!(
@@ -303,56 +656,53 @@ impl Deduction {
     pub fn specification(
         &mut self,
         n: usize,
-        var_name: &'static str,
+        var_name: &str,
         term: &Term,
     ) -> Result<(), LogicError> {
-        let t = specification(self.get_theorem(n)?, &var_name, term)?;
-        let r = format!("specification of {var_name} to {term} in theorem {n}");
-        self.push_new(t, r, Rule::Specification);
+        let t = self.get_certificate(n)?.specification(var_name, term)?;
+        let j = Justification::Specification {
+            theorem: n,
+            var_name: var_name.to_string(),
+            term: term.clone(),
+        };
+        self.push_new(t, j);
         Ok(())
     }
 
     /// Push a new theorem that adds universal quantification of var in theorem n.
-    pub fn generalization(&mut self, n: usize, var_name: &'static str) -> Result<(), LogicError> {
-        if self.depth() != 0 {
-            let mut free_vars = IndexSet::<String>::new();
-            self.get_theorem(self.scope_cur)?
-                .get_vars_free(&mut free_vars);
-            if free_vars.contains(&var_name.to_string()) {
-                let msg = format!(
-                    "Generalization Error: the variable {var_name} is free in the supposition {}",
-                    self.get_theorem(self.scope_cur)?
-                );
-                return Err(LogicError::new(msg));
-            }
-        }
-        let t = generalization(self.get_theorem(n)?, var_name);
-        let r = format!("generalization of {var_name} in theorem {n}");
-        self.push_new(t?, r, Rule::Generalization);
+    pub fn generalization(&mut self, n: usize, var_name: &str) -> Result<(), LogicError> {
+        self.check_unsupposed(var_name, "Generalization")?;
+        let t = self.get_certificate(n)?.generalization(var_name);
+        let j = Justification::Generalization {
+            theorem: n,
+            var_name: var_name.to_string(),
+        };
+        self.push_new(t?, j);
         Ok(())
     }
 
     /// Push a new theorem that adds existence quantification of var in theorem n.
     pub fn existence(&mut self, n: usize, var_name: &str) -> Result<(), LogicError> {
-        let t = Formula::exists(var_name, &self.theorems[n].formula.clone());
-        let r = format!("existence of {var_name} in theorem {n}");
-        self.push_new(t, r, Rule::Existence);
+        let t = self.get_certificate(n)?.existence(var_name);
+        let j = Justification::Existence {
+            theorem: n,
+            var_name: var_name.to_string(),
+        };
+        self.push_new(t?, j);
         Ok(())
     }
 
     /// Push a new theorem that applies the successor to each side of a theorem n.
     pub fn successor(&mut self, n: usize) -> Result<(), LogicError> {
-        let t = successor(self.get_theorem(n)?);
-        let r = format!("successor of theorem {n}");
-        self.push_new(t?, r, Rule::Successor);
+        let t = self.get_certificate(n)?.successor();
+        self.push_new(t?, Justification::Successor(n));
         Ok(())
     }
 
     /// Push a new theorem that strips the successor to each side of a theorem n.
     pub fn predecessor(&mut self, n: usize) -> Result<(), LogicError> {
-        let t = predecessor(self.get_theorem(n)?);
-        let r = format!("predecessor of theorem {n}");
-        self.push_new(t?, r, Rule::Predecessor);
+        let t = self.get_certificate(n)?.predecessor();
+        self.push_new(t?, Justification::Predecessor(n));
         Ok(())
     }
 
@@ -363,9 +713,13 @@ impl Deduction {
         var_name: &str,
         pos: usize,
     ) -> Result<(), LogicError> {
-        let t = interchange_ea(self.get_theorem(n)?, var_name, pos);
-        let r = format!("interchange ~E{var_name}: for A{var_name}:~ in theorem {n}");
-        self.push_new(t?, r, Rule::InterchangeEA);
+        let t = self.get_certificate(n)?.interchange_ea(var_name, pos);
+        let j = Justification::InterchangeEA {
+            theorem: n,
+            var_name: var_name.to_string(),
+            position: pos,
+        };
+        self.push_new(t?, j);
         Ok(())
     }
 
@@ -376,25 +730,68 @@ impl Deduction {
         var_name: &str,
         pos: usize,
     ) -> Result<(), LogicError> {
-        let t = interchange_ae(self.get_theorem(n)?, var_name, pos);
-        let r = format!("interchange A{var_name}:~ for ~E{var_name}: in theorem {n}");
-        self.push_new(t?, r, Rule::InterchangeAE);
+        let t = self.get_certificate(n)?.interchange_ae(var_name, pos);
+        let j = Justification::InterchangeAE {
+            theorem: n,
+            var_name: var_name.to_string(),
+            position: pos,
+        };
+        self.push_new(t?, j);
         Ok(())
     }
 
     /// Push a new theorem that flips the left and right sides of theorem n.
     pub fn symmetry(&mut self, n: usize) -> Result<(), LogicError> {
-        let t = symmetry(self.get_theorem(n)?);
-        let r = format!("symmetry of theorem {n}");
-        self.push_new(t?, r, Rule::Symmetry);
+        let t = self.get_certificate(n)?.symmetry();
+        self.push_new(t?, Justification::Symmetry(n));
         Ok(())
     }
 
     /// Push a new theorem that is an equality of the left term and right term of formula n1 and n2.
     pub fn transitivity(&mut self, n1: usize, n2: usize) -> Result<(), LogicError> {
-        let t = transitivity(self.get_theorem(n1)?, self.get_theorem(n2)?);
-        let r = format!("transitivity of theorem {n1} and theorem {n2}");
-        self.push_new(t?, r, Rule::Transitivity);
+        let t = self
+            .get_certificate(n1)?
+            .transitivity(self.get_certificate(n2)?);
+        self.push_new(t?, Justification::Transitivity(n1, n2));
+        Ok(())
+    }
+
+    /// Push a new theorem that is the contrapositive of the implication in theorem n.
+    pub fn contrapositive(&mut self, n: usize) -> Result<(), LogicError> {
+        let t = self.get_certificate(n)?.contrapositive();
+        self.push_new(t?, Justification::Contrapositive(n));
+        Ok(())
+    }
+
+    /// Push a new theorem that is the right side of the implication in theorem n2 when theorem n1 is its left side.
+    pub fn detachment(&mut self, n1: usize, n2: usize) -> Result<(), LogicError> {
+        let t = self
+            .get_certificate(n1)?
+            .detachment(self.get_certificate(n2)?);
+        let j = Justification::Detachment {
+            premise: n1,
+            implication: n2,
+        };
+        self.push_new(t?, j);
+        Ok(())
+    }
+
+    /// Push a new theorem that replaces the left side of the equality in theorem n1 with its right side at each of the paths in theorem n2. This is an extension rule, see Formula::term_at for the form of the paths.
+    pub fn substitution(
+        &mut self,
+        n1: usize,
+        n2: usize,
+        paths: &[Vec<usize>],
+    ) -> Result<(), LogicError> {
+        let t = self
+            .get_certificate(n1)?
+            .substitution(self.get_certificate(n2)?, paths);
+        let j = Justification::Substitution {
+            equality: n1,
+            theorem: n2,
+            paths: paths.to_vec(),
+        };
+        self.push_new(t?, j);
         Ok(())
     }
 
@@ -403,22 +800,23 @@ impl Deduction {
         // Push the current scope onto the stack and name the new scope after the index where it starts
         self.scope_stack.push(self.scope_cur);
         self.scope_cur = self.index;
-        self.push_new(premise, "supposition".to_string(), Rule::Supposition);
+        let t = Theorem::supposition(&premise);
+        self.push_new(t, Justification::Supposition);
         Ok(())
     }
 
     /// End a supposition and push a new theorem that the premise of the supposition implies the final theorem of the supposition.
     pub fn implication(&mut self) -> Result<(), LogicError> {
-        // Create the formula and annotation
-        let t = Formula::implies(self.get_theorem(self.scope_cur)?, self.get_last_theorem());
-        let r = format!(
-            "implication of theorem {} and theorem {}",
-            self.scope_cur, self.index
-        );
+        // Create the formula and justification
+        let t = Theorem::implication(self.get_theorem(self.scope_cur)?, self.get_last_theorem());
+        let j = Justification::Implication {
+            premise: self.scope_cur,
+            conclusion: self.index - 1,
+        };
 
         // Pop the top of the stack and make it the new scope
         self.scope_cur = self.scope_stack.pop().unwrap();
-        self.push_new(t, r, Rule::Implication);
+        self.push_new(t, j);
         Ok(())
     }
 
@@ -429,20 +827,65 @@ impl Deduction {
         base: usize,
         general: usize,
     ) -> Result<(), LogicError> {
-        let t = induction(
+        self.check_unsupposed(var_name, "Induction")?;
+        let t = Theorem::induction(
             var_name,
-            self.get_theorem(base)?,
-            self.get_theorem(general)?,
+            self.get_certificate(base)?,
+            self.get_certificate(general)?,
         );
-        let r = format!("induction of {var_name} on theorems {base} and {general}");
-        self.push_new(t?, r, Rule::Induction);
+        let j = Justification::Induction {
+            var_name: var_name.to_string(),
+            base,
+            general,
+        };
+        self.push_new(t?, j);
         Ok(())
     }
 
+    /// Derive again every theorem of another Deduction, which must use the same axioms and have no open suppositions, and return the position of its last theorem. References to earlier theorems in the justifications are renumbered. If any line cannot be derived here, for example because it quantifies a variable that is free in an open supposition, nothing is added.
+    /// ```
+    /// # use tnt::{lemmas, Deduction, Term};
+    /// # use std::convert::TryFrom;
+    /// let (commutes, _) = lemmas::addition_commutes().unwrap();
+    /// let mut d = Deduction::new("Two Plus One");
+    /// let n = d.import(&commutes).unwrap();
+    /// d.specification(n, "c", &Term::try_from("SS0").unwrap()).unwrap();
+    /// d.specification(n + 1, "d", &Term::one()).unwrap();
+    /// assert_eq!(d.last_theorem().formula().to_string(), "(SS0+S0)=(S0+SS0)");
+    /// ```
+    pub fn import(&mut self, other: &Deduction) -> Result<usize, LogicError> {
+        if self.axioms != other.axioms {
+            return Err(LogicError::new(format!(
+                "Import Error: the Deduction `{}` does not use the same axioms",
+                other.title
+            )));
+        }
+        if other.theorems.is_empty() || other.depth() != 0 {
+            return Err(LogicError::new(format!(
+                "Import Error: the Deduction `{}` does not have a conclusion outside of a supposition",
+                other.title
+            )));
+        }
+        // Every line is derived again here so the restrictions on quantifying the variables of open suppositions apply and nothing recorded in the other Deduction is trusted
+        let offset = self.index;
+        self.atomic(|d| {
+            for t in other.theorems.iter() {
+                d.apply_expecting(&t.justification.offset(offset), t.formula())
+                    .map_err(|e| {
+                        LogicError::new(format!(
+                            "Import Error: theorem {} of `{}` cannot be added, {e}",
+                            t.position, other.title
+                        ))
+                    })?;
+            }
+            Ok(d.index - 1)
+        })
+    }
+
     fn vars_in_order(&self) -> IndexSet<String> {
         let mut vars = IndexSet::new();
         for theorem in self.theorems.iter() {
-            theorem.formula.get_vars(&mut vars)
+            theorem.formula().get_vars(&mut vars)
         }
         vars
     }
@@ -452,28 +895,24 @@ impl Deduction {
         let vars = self.vars_in_order();
         let mut out = self.clone();
         for theorem in out.theorems.iter_mut() {
-            theorem.formula.to_austere_with(&vars);
+            theorem.theorem.to_austere_with(&vars);
         }
         out
     }
 
     /// Change all Formulas in the Deduction to their austere form.
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_austere(&mut self) {
         let vars = self.vars_in_order();
         for theorem in self.theorems.iter_mut() {
-            theorem.formula.to_austere_with(&vars);
+            theorem.theorem.to_austere_with(&vars);
         }
     }
 
     /// Convert the Deduction to a (very large) integer. Formulas are separated by the the ASCII null symbol.
     pub fn arithmetize(&self) -> BigUint {
-        let austere = self.austere();
-        let mut n: Vec<u8> = Vec::new();
-        for t in austere.theorems().rev() {
-            n.extend(t.formula.to_string().into_bytes().iter());
-            n.push(0);
-        }
-        BigUint::from_bytes_be(&n)
+        self.arithmetize_with(&ByteNumbering)
+            .expect("the byte numbering can encode any string")
     }
 }
 
@@ -488,14 +927,8 @@ impl fmt::Display for Deduction {
             } else if t.depth < prev_depth {
                 let end = format!("\n{}end supposition", "   ".repeat(t.depth));
                 out.push_str(&end);
-            } else {
             }
-            let line = format!(
-                "\n{}{}) {}",
-                "   ".repeat(t.depth),
-                pos,
-                t.formula.to_string()
-            );
+            let line = format!("\n{}{}) {}", "   ".repeat(t.depth), pos, t.formula());
             out.push_str(&line);
             prev_depth = t.depth;
         }
@@ -503,13 +936,305 @@ impl fmt::Display for Deduction {
     }
 }
 
-// impl TryFrom<BigUint> for Deduction {
-//     type Error = LogicError;
+impl TryFrom<BigUint> for Deduction {
+    type Error = LogicError;
 
-//     fn try_from(value: BigUint) -> Result<Self, Self::Error> {
-//         match from_utf8(&value.to_bytes_be()) {
-//             Ok(s) => Formula::try_from(s),
-//             Err(e) => Err(LogicError(e.to_string())),
-//         }
-//     }
-// }
+    /// Reconstruct a Deduction from the number produced by arithmetize, as from_arithmetized does for the byte numbering.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("One Plus Zero");
+    /// d.add_axiom(1).unwrap();
+    /// d.specification(0, "a", &Term::one()).unwrap();
+    /// let e = Deduction::try_from(d.arithmetize()).unwrap();
+    /// assert_eq!(e.last_theorem().formula().to_string(), "(S0+0)=S0");
+    /// ```
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        Deduction::from_arithmetized(&value, &ByteNumbering)
+    }
+}
+
+impl Deduction {
+    /// Convert the Deduction to a (very large) integer in the given numbering, or an error if the numbering cannot encode it. The Formulas are written from last to first, each followed by the separator of the numbering.
+    pub fn arithmetize_with(&self, numbering: &impl GodelNumbering) -> Result<BigUint, LogicError> {
+        let austere = self.austere();
+        let mut s = String::new();
+        for t in austere.theorems().rev() {
+            s.push_str(&t.formula().to_string());
+            s.push('\0');
+        }
+        numbering.encode(&s)
+    }
+
+    /// Reconstruct a Deduction from the number produced by arithmetize_with in the same numbering. Only the Formulas are encoded so a Justification is inferred for every line, along with where each supposition begins and ends, and an error is returned if the number does not encode a valid Deduction. The title is left empty.
+    ///
+    /// Since the variables are renamed the axioms of the Deduction are the Peano axioms with their variables renamed to match any line that states one of them.
+    ///
+    /// A line that could begin or end a supposition as well as follow from earlier lines is tried each way, so a crafted number can need a long search. It is bounded as described for godel::is_proof_pair_with and an error is returned if it gives up.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// # use tnt::numbering::CodonNumbering;
+    /// let mut d = Deduction::new("One Plus Zero");
+    /// d.add_axiom(1).unwrap();
+    /// d.specification(0, "a", &Term::one()).unwrap();
+    /// let n = d.arithmetize_with(&CodonNumbering).unwrap();
+    /// let e = Deduction::from_arithmetized(&n, &CodonNumbering).unwrap();
+    /// assert_eq!(e.last_theorem().formula().to_string(), "(S0+0)=S0");
+    /// ```
+    pub fn from_arithmetized(
+        number: &BigUint,
+        numbering: &impl GodelNumbering,
+    ) -> Result<Deduction, LogicError> {
+        Ok(Deduction::from_austere_lines(
+            &Deduction::arithmetized_lines(number, numbering)?,
+        )?)
+    }
+
+    // The Formulas encoded by a number produced by arithmetize_with, from first to last.
+    pub(crate) fn arithmetized_lines(
+        number: &BigUint,
+        numbering: &impl GodelNumbering,
+    ) -> Result<Vec<Formula>, LogicError> {
+        let text = numbering.decode(number)?;
+        let text = text.strip_suffix('\0').unwrap_or(&text);
+        let mut formulas = Vec::new();
+        if !text.is_empty() {
+            // The lines are encoded from last to first
+            for s in text.split('\0').rev() {
+                match Formula::try_from(s) {
+                    Ok(f) if f.to_string() == s => formulas.push(f),
+                    _ => {
+                        return Err(LogicError(format!(
+                            "Arithmetization Error: `{s}` is not a well formed Formula"
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(formulas)
+    }
+
+    // Infer a Deduction from Formulas in austere form, with the Peano axioms renamed to match any line that states one of them.
+    pub(crate) fn from_austere_lines(formulas: &[Formula]) -> Result<Deduction, SearchError> {
+        let axioms = PEANO_AXIOMS
+            .iter()
+            .map(|axiom| {
+                let austere = axiom.austere();
+                formulas
+                    .iter()
+                    .find(|f| f.austere() == austere)
+                    .unwrap_or(axiom)
+                    .clone()
+            })
+            .collect();
+        reconstruct("", axioms, formulas)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_atomic() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Atomic");
+        d.add_axiom(1)?;
+        let result = d.atomic(|d| {
+            d.supposition(Formula::try_from("a=0")?)?;
+            d.symmetry(1)?;
+            d.add_axiom(9)
+        });
+        assert!(result.is_err());
+        assert_eq!(d.theorems().len(), 1);
+        assert_eq!(d.depth(), 0);
+        assert_eq!(d.scopes(), (&[0][..], 0));
+        d.specification(0, "a", &Term::zero())?;
+        assert_eq!(d.last_theorem().position, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_justification() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Justification");
+        d.add_axiom(1)?;
+        d.specification(0, "a", &Term::one())?;
+        d.supposition(Formula::try_from("S0=b")?)?;
+        d.transitivity(1, 2)?;
+        d.implication()?;
+        assert_eq!(
+            d.theorem(3).justification,
+            Justification::Transitivity(1, 2)
+        );
+        assert_eq!(d.theorem(4).justification.premises(), vec![2, 3]);
+        assert_eq!(
+            d.theorem(4).annotation(),
+            "implication of theorem 2 and theorem 3"
+        );
+        assert_eq!(
+            d.theorem(1).annotation(),
+            "specification of a to S0 in theorem 0"
+        );
+        assert_eq!(d.theorem(0).rule(), Rule::Axiom);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scope_events() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Scopes");
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.supposition(Formula::try_from("b=0")?)?;
+        d.implication()?;
+        d.supposition(Formula::try_from("c=0")?)?;
+        let events: Vec<String> = d
+            .scope_events()
+            .map(|e| match e {
+                ScopeEvent::Open(t) => format!("open {}", t.position),
+                ScopeEvent::Line(t) => t.position.to_string(),
+                ScopeEvent::Close(depth) => format!("close to {depth}"),
+            })
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "open 0",
+                "0",
+                "open 1",
+                "1",
+                "close to 1",
+                "2",
+                "open 3",
+                "3",
+                "close to 1",
+                "close to 0"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_discharged_supposition() -> Result<(), LogicError> {
+        // the supposition on line 0 is discharged before another is opened
+        let mut d = Deduction::new("Discharged");
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.implication()?;
+        d.supposition(Formula::try_from("b=0")?)?;
+        d.generalization(2, "a")?;
+        assert!(d.generalization(2, "b").is_err());
+        let vars: Vec<String> = d.supposition_vars().into_iter().collect();
+        assert_eq!(vars, ["b"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_import() -> Result<(), LogicError> {
+        let mut lemma = Deduction::new("Lemma");
+        lemma.supposition(Formula::try_from("a=b")?)?;
+        lemma.symmetry(0)?;
+        lemma.implication()?;
+
+        let mut d = Deduction::new("Import");
+        d.add_axiom(0)?;
+        let n = d.import(&lemma)?;
+        assert_eq!(n, 3);
+        assert_eq!(d.theorem(2).justification, Justification::Symmetry(1));
+        assert_eq!(d.theorem(3).formula().to_string(), "[a=b>b=a]");
+        assert_eq!(d.depth(), 0);
+        // theorems inside the imported supposition are not accessible
+        assert!(d.symmetry(2).is_err());
+        d.generalization(3, "a")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_err() -> Result<(), LogicError> {
+        let mut lemma = Deduction::new("Lemma");
+        lemma.add_axiom(1)?;
+        lemma.specification(0, "a", &Term::var("c"))?;
+        lemma.generalization(1, "c")?;
+
+        let mut custom = Deduction::custom("Custom", vec![]);
+        assert!(custom.import(&lemma).is_err());
+        let mut d = Deduction::new("Import");
+        assert!(d.import(&Deduction::new("Empty")).is_err());
+        d.supposition(Formula::try_from("c=0")?)?;
+        assert!(d.import(&lemma).is_err());
+        assert_eq!(d.theorems().len(), 1);
+
+        // induction quantifies its variable too
+        let mut lemma = Deduction::new("Lemma");
+        lemma.zero_sum_lemma("b")?;
+        let mut d = Deduction::new("Import");
+        d.supposition(Formula::try_from("b=0")?)?;
+        assert!(d.import(&lemma).is_err());
+        assert_eq!(d.theorems().len(), 1);
+        assert_eq!(d.depth(), 1);
+        if let Justification::Induction { base, general, .. } = lemma.last_theorem().justification {
+            lemma.supposition(Formula::try_from("b=0")?)?;
+            assert!(lemma.induction("b", base, general).is_err());
+        }
+
+        // recorded formulas are not trusted
+        let mut lemma = Deduction::new("Lemma");
+        lemma.supposition(Formula::try_from("a=b")?)?;
+        lemma.symmetry(0)?;
+        lemma.implication()?;
+        lemma.theorems_mut()[1].theorem = lemma.theorem(0).theorem.clone();
+        let mut d = Deduction::new("Import");
+        assert!(d.import(&lemma).is_err());
+        assert_eq!(d.theorems().len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_biguint() -> Result<(), LogicError> {
+        for (d, _) in [
+            crate::lemmas::addition_commutes()?,
+            crate::lemmas::addition_cancels()?,
+        ] {
+            let e = Deduction::try_from(d.arithmetize())?;
+            crate::check(&e)?;
+            assert_eq!(e.arithmetize(), d.arithmetize());
+            for (t1, t2) in d.austere().theorems().zip(e.theorems()) {
+                assert_eq!(t1.formula(), t2.formula());
+                assert_eq!((t1.depth, t1.scope), (t2.depth, t2.scope));
+            }
+        }
+        assert_eq!(
+            Deduction::try_from(BigUint::from(0u32))?.theorems().len(),
+            0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_from_biguint_err() -> Result<(), LogicError> {
+        let encode = |s: &str| BigUint::from_bytes_be(s.as_bytes());
+        assert!(Deduction::try_from(encode("0=S0\0")).is_err());
+        assert!(Deduction::try_from(encode("(a+0)=a\0Aa:(a+0)=a\0")).is_ok());
+        // lines in the wrong order
+        assert!(Deduction::try_from(encode("Aa:(a+0)=a\0(a+0)=a\0")).is_err());
+        assert!(Deduction::try_from(encode("a=\0")).is_err());
+        // a supposition that is never ended
+        let mut d = Deduction::new("Open");
+        d.supposition(Formula::try_from("a=b")?)?;
+        d.symmetry(0)?;
+        assert!(Deduction::try_from(d.arithmetize()).is_err());
+        d.implication()?;
+        assert!(Deduction::try_from(d.arithmetize()).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_codon_numbering() -> Result<(), LogicError> {
+        use crate::numbering::CodonNumbering;
+        let (d, _) = crate::lemmas::addition_commutes()?;
+        let n = d.arithmetize_with(&CodonNumbering)?;
+        // every formula is followed by the punctuation codon
+        assert!(n.to_string().ends_with("611"));
+        let e = Deduction::from_arithmetized(&n, &CodonNumbering)?;
+        assert_eq!(e.arithmetize(), d.arithmetize());
+        assert!(Deduction::from_arithmetized(&d.arithmetize(), &CodonNumbering).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,805 @@
+//! General facts of arithmetic proven by induction from the Peano axioms. Tactics prove these once within a Deduction and then use them by specification. The names of the universally quantified variables are chosen by the caller so that they can be specified to any Term without a clash.
+
+use indexmap::IndexSet;
+
+use std::convert::TryFrom;
+
+use crate::{Deduction, Formula, LogicError, Term};
+
+// Only called on strings that are built to be well-formed.
+fn formula(s: &str) -> Formula {
+    Formula::try_from(s).expect("derivations only build well-formed Formulas")
+}
+
+fn term(s: &str) -> Term {
+    Term::try_from(s).expect("derivations only build well-formed Terms")
+}
+
+// The first n variable names, taking every letter from a to z before adding a prime, that are not in the set to avoid.
+pub(crate) fn fresh_names(avoid: &IndexSet<String>, n: usize) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut primes = String::new();
+    while out.len() < n {
+        for letter in 'a'..='z' {
+            let name = format!("{letter}{primes}");
+            if !avoid.contains(&name) && out.len() < n {
+                out.push(name);
+            }
+        }
+        primes.push('\'');
+    }
+    out
+}
+
+impl Deduction {
+    // Position of the last theorem.
+    pub(crate) fn last(&self) -> usize {
+        self.last_theorem().position
+    }
+
+    // Choose n variable names that appear in none of the Terms given, the axioms, or the premise of an open supposition.
+    pub(crate) fn fresh_vars(&self, terms: &[&Term], n: usize) -> Vec<String> {
+        let mut avoid = self.supposition_vars();
+        for axiom in self.axioms() {
+            axiom.get_vars(&mut avoid);
+        }
+        for t in terms {
+            t.get_vars(&mut avoid);
+        }
+        fresh_names(&avoid, n)
+    }
+
+    // Specify the leading universally quantified variables of a lemma to the given Terms.
+    pub(crate) fn instance(
+        &mut self,
+        lemma: usize,
+        vars: &[String],
+        terms: &[&Term],
+    ) -> Result<usize, LogicError> {
+        let pairs: Vec<(&str, &Term)> = vars
+            .iter()
+            .map(|v| v.as_str())
+            .zip(terms.iter().copied())
+            .collect();
+        self.specify_all(lemma, &pairs)
+    }
+
+    // Position of the lemma if it has already been proven.
+    fn lemma_goal(&self, goal: &str) -> Option<usize> {
+        self.find_theorem(&formula(goal))
+    }
+
+    // Generalize the variables in reverse order so the first is outermost.
+    fn generalize_all(&mut self, vars: &[&str]) -> Result<usize, LogicError> {
+        for v in vars.iter().rev() {
+            self.generalization(self.last(), v)?;
+        }
+        Ok(self.last())
+    }
+
+    /// Ax:(0+x)=x
+    pub(crate) fn zero_sum_lemma(&mut self, x: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:(0+{x})={x}")) {
+            return Ok(pos);
+        }
+        let zero = &Term::zero();
+        let ax1 = self.peano_axiom(1)?;
+        let base = self.specify_all(ax1, &[("a", zero)])?;
+        let ax2 = self.peano_axiom(2)?;
+        let step = self.specify_all(ax2, &[("a", zero), ("b", &term(x))])?;
+
+        self.supposition(formula(&format!("(0+{x})={x}")))?;
+        self.successor(self.last())?;
+        self.transitivity(step, self.last())?;
+        self.implication()?;
+        self.generalization(self.last(), x)?;
+        self.induction(x, base, self.last())?;
+        Ok(self.last())
+    }
+
+    /// Ax:Ay:(Sx+y)=S(x+y)
+    pub(crate) fn succ_sum_lemma(&mut self, x: &str, y: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:(S{x}+{y})=S({x}+{y})")) {
+            return Ok(pos);
+        }
+        let (xt, yt, sx) = (&term(x), &term(y), &term(&format!("S{x}")));
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+
+        // (Sx+0)=Sx and Sx=S(x+0)
+        let p1 = self.specify_all(ax1, &[("a", sx)])?;
+        let p2 = self.specify_all(ax1, &[("a", xt)])?;
+        let p3 = self.successor_times(p2, 1)?;
+        let p4 = self.symmetry_reuse(p3)?;
+        let base = self.transitivity_reuse(p1, p4)?;
+
+        // (Sx+Sy)=S(Sx+y) and SS(x+y)=S(x+Sy)
+        let q1 = self.specify_all(ax2, &[("a", sx), ("b", yt)])?;
+        let q2 = self.specify_all(ax2, &[("a", xt), ("b", yt)])?;
+        let q3 = self.successor_times(q2, 1)?;
+        let q4 = self.symmetry_reuse(q3)?;
+
+        self.supposition(formula(&format!("(S{x}+{y})=S({x}+{y})")))?;
+        let s = self.successor_times(self.last(), 1)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q4)?;
+        self.implication()?;
+        self.generalization(self.last(), y)?;
+        self.induction(y, base, self.last())?;
+        self.generalize_all(&[x])
+    }
+
+    /// Ax:Ay:(x+y)=(y+x)
+    pub(crate) fn sum_commutes_lemma(&mut self, x: &str, y: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:({x}+{y})=({y}+{x})")) {
+            return Ok(pos);
+        }
+        let (xt, yt) = (&term(x), &term(y));
+        let v = self.fresh_vars(&[xt, yt], 2);
+        let zero_sum = self.zero_sum_lemma(&v[0])?;
+        let succ_sum = self.succ_sum_lemma(&v[0], &v[1])?;
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+
+        // (x+0)=x=(0+x)
+        let p1 = self.specify_all(ax1, &[("a", xt)])?;
+        let p2 = self.instance(zero_sum, &v, &[xt])?;
+        let p3 = self.symmetry_reuse(p2)?;
+        let base = self.transitivity_reuse(p1, p3)?;
+
+        // (x+Sy)=S(x+y) and S(y+x)=(Sy+x)
+        let q1 = self.specify_all(ax2, &[("a", xt), ("b", yt)])?;
+        let q2 = self.instance(succ_sum, &v, &[yt, xt])?;
+        let q3 = self.symmetry_reuse(q2)?;
+
+        self.supposition(formula(&format!("({x}+{y})=({y}+{x})")))?;
+        let s = self.successor_times(self.last(), 1)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q3)?;
+        self.implication()?;
+        self.generalization(self.last(), y)?;
+        self.induction(y, base, self.last())?;
+        self.generalize_all(&[x])
+    }
+
+    /// Ax:Ay:Az:[x=y>(x+z)=(y+z)]
+    pub(crate) fn sum_congruence_left_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:A{z}:[{x}={y}>({x}+{z})=({y}+{z})]"))
+        {
+            return Ok(pos);
+        }
+        let (xt, yt, zt) = (&term(x), &term(y), &term(z));
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+
+        // (x+0)=x and y=(y+0)
+        let p1 = self.specify_all(ax1, &[("a", xt)])?;
+        let p2 = self.specify_all(ax1, &[("a", yt)])?;
+        let p3 = self.symmetry_reuse(p2)?;
+        self.supposition(formula(&format!("{x}={y}")))?;
+        let s = self.transitivity_reuse(p1, self.last())?;
+        self.transitivity(s, p3)?;
+        self.implication()?;
+        let base = self.last();
+
+        // (x+Sz)=S(x+z) and S(y+z)=(y+Sz)
+        let q1 = self.specify_all(ax2, &[("a", xt), ("b", zt)])?;
+        let q2 = self.specify_all(ax2, &[("a", yt), ("b", zt)])?;
+        let q3 = self.symmetry_reuse(q2)?;
+        self.supposition(formula(&format!("[{x}={y}>({x}+{z})=({y}+{z})]")))?;
+        let hyp = self.last();
+        self.supposition(formula(&format!("{x}={y}")))?;
+        self.detachment(self.last(), hyp)?;
+        let s = self.successor_times(self.last(), 1)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q3)?;
+        self.implication()?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:Ay:Az:[x=y>(z+x)=(z+y)]
+    pub(crate) fn sum_congruence_right_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:A{z}:[{x}={y}>({z}+{x})=({z}+{y})]"))
+        {
+            return Ok(pos);
+        }
+        let (xt, yt, zt) = (&term(x), &term(y), &term(z));
+        let v = self.fresh_vars(&[xt, yt, zt], 2);
+        let zero_sum = self.zero_sum_lemma(&v[0])?;
+        let succ_sum = self.succ_sum_lemma(&v[0], &v[1])?;
+
+        // (0+x)=x and y=(0+y)
+        let p1 = self.instance(zero_sum, &v, &[xt])?;
+        let p2 = self.instance(zero_sum, &v, &[yt])?;
+        let p3 = self.symmetry_reuse(p2)?;
+        self.supposition(formula(&format!("{x}={y}")))?;
+        let s = self.transitivity_reuse(p1, self.last())?;
+        self.transitivity(s, p3)?;
+        self.implication()?;
+        let base = self.last();
+
+        // (Sz+x)=S(z+x) and S(z+y)=(Sz+y)
+        let q1 = self.instance(succ_sum, &v, &[zt, xt])?;
+        let q2 = self.instance(succ_sum, &v, &[zt, yt])?;
+        let q3 = self.symmetry_reuse(q2)?;
+        self.supposition(formula(&format!("[{x}={y}>({z}+{x})=({z}+{y})]")))?;
+        let hyp = self.last();
+        self.supposition(formula(&format!("{x}={y}")))?;
+        self.detachment(self.last(), hyp)?;
+        let s = self.successor_times(self.last(), 1)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q3)?;
+        self.implication()?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:Ay:Az:((x+y)+z)=(x+(y+z))
+    pub(crate) fn sum_associates_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) =
+            self.lemma_goal(&format!("A{x}:A{y}:A{z}:(({x}+{y})+{z})=({x}+({y}+{z}))"))
+        {
+            return Ok(pos);
+        }
+        let (xt, yt, zt) = (&term(x), &term(y), &term(z));
+        let (xy, yz) = (&Term::sum(xt, yt), &Term::sum(yt, zt));
+        let v = self.fresh_vars(&[xt, yt, zt], 3);
+        let right = self.sum_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+
+        // ((x+y)+0)=(x+y)=(x+(y+0))
+        let p1 = self.specify_all(ax1, &[("a", xy)])?;
+        let p2 = self.specify_all(ax1, &[("a", yt)])?;
+        let p3 = self.instance(right, &v, &[&Term::sum(yt, &Term::zero()), yt, xt])?;
+        let p4 = self.detachment_reuse(p2, p3)?;
+        let p5 = self.symmetry_reuse(p4)?;
+        let base = self.transitivity_reuse(p1, p5)?;
+
+        // ((x+y)+Sz)=S((x+y)+z) and S(x+(y+z))=(x+(y+Sz))
+        let q1 = self.specify_all(ax2, &[("a", xy), ("b", zt)])?;
+        let q2 = self.specify_all(ax2, &[("a", yt), ("b", zt)])?;
+        let q3 = self.instance(
+            right,
+            &v,
+            &[&Term::sum(yt, &Term::succ(zt)), &Term::succ(yz), xt],
+        )?;
+        let q4 = self.detachment_reuse(q2, q3)?;
+        let q5 = self.specify_all(ax2, &[("a", xt), ("b", yz)])?;
+        let q6 = self.transitivity_reuse(q4, q5)?;
+        let q7 = self.symmetry_reuse(q6)?;
+
+        self.supposition(formula(&format!("(({x}+{y})+{z})=({x}+({y}+{z}))")))?;
+        let s = self.successor_times(self.last(), 1)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q7)?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:(0*x)=0
+    pub(crate) fn zero_product_lemma(&mut self, x: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:(0*{x})=0")) {
+            return Ok(pos);
+        }
+        let (zero, xt) = (&Term::zero(), &term(x));
+        let ax1 = self.peano_axiom(1)?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+        let base = self.specify_all(ax3, &[("a", zero)])?;
+
+        // (0*Sx)=(0*x)
+        let q1 = self.specify_all(ax4, &[("a", zero), ("b", xt)])?;
+        let q2 = self.specify_all(ax1, &[("a", &Term::prod(zero, xt))])?;
+        let q3 = self.transitivity_reuse(q1, q2)?;
+
+        self.supposition(formula(&format!("(0*{x})=0")))?;
+        self.transitivity(q3, self.last())?;
+        self.implication()?;
+        self.generalization(self.last(), x)?;
+        self.induction(x, base, self.last())?;
+        Ok(self.last())
+    }
+
+    /// Ax:Ay:(Sx*y)=((x*y)+y)
+    pub(crate) fn succ_product_lemma(&mut self, x: &str, y: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:(S{x}*{y})=(({x}*{y})+{y})")) {
+            return Ok(pos);
+        }
+        let (xt, yt, sx, sy) = (
+            &term(x),
+            &term(y),
+            &term(&format!("S{x}")),
+            &term(&format!("S{y}")),
+        );
+        let xy = &Term::prod(xt, yt);
+        let v = self.fresh_vars(&[xt, yt], 3);
+        let left = self.sum_congruence_left_lemma(&v[0], &v[1], &v[2])?;
+        let right = self.sum_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+        let assoc = self.sum_associates_lemma(&v[0], &v[1], &v[2])?;
+        let comm = self.sum_commutes_lemma(&v[0], &v[1])?;
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+
+        // (Sx*0)=0=((x*0)+0)
+        let p1 = self.specify_all(ax3, &[("a", sx)])?;
+        let x0 = &Term::prod(xt, &Term::zero());
+        let p2 = self.specify_all(ax1, &[("a", x0)])?;
+        let p3 = self.specify_all(ax3, &[("a", xt)])?;
+        let p4 = self.transitivity_reuse(p2, p3)?;
+        let p5 = self.symmetry_reuse(p4)?;
+        let base = self.transitivity_reuse(p1, p5)?;
+
+        // (((x*y)+y)+x)=(((x*y)+x)+y) by associativity and commutativity
+        let xyy = &Term::sum(xy, yt);
+        let xyx = &Term::sum(xy, xt);
+        let m1 = self.instance(assoc, &v, &[xy, yt, xt])?;
+        let m2 = self.instance(comm, &v, &[yt, xt])?;
+        let m3 = self.instance(right, &v, &[&Term::sum(yt, xt), &Term::sum(xt, yt), xy])?;
+        let m4 = self.detachment_reuse(m2, m3)?;
+        let m5 = self.instance(assoc, &v, &[xy, xt, yt])?;
+        let m6 = self.symmetry_reuse(m5)?;
+        let m7 = self.transitivity_reuse(m1, m4)?;
+        let m8 = self.transitivity_reuse(m7, m6)?;
+        let m9 = self.successor_times(m8, 1)?;
+
+        // S(((x*y)+x)+y)=((x*Sy)+Sy)
+        let t1 = self.specify_all(ax4, &[("a", xt), ("b", yt)])?;
+        let t2 = self.instance(left, &v, &[&Term::prod(xt, sy), xyx, sy])?;
+        let t3 = self.detachment_reuse(t1, t2)?;
+        let t4 = self.specify_all(ax2, &[("a", xyx), ("b", yt)])?;
+        let t5 = self.transitivity_reuse(t3, t4)?;
+        let t6 = self.symmetry_reuse(t5)?;
+
+        // (Sx*Sy)=((Sx*y)+Sx) and (((x*y)+y)+Sx)=S(((x*y)+y)+x)
+        let q1 = self.specify_all(ax4, &[("a", sx), ("b", yt)])?;
+        let q2 = self.instance(left, &v, &[&Term::prod(sx, yt), xyy, sx])?;
+        let q3 = self.specify_all(ax2, &[("a", xyy), ("b", xt)])?;
+
+        self.supposition(formula(&format!("(S{x}*{y})=(({x}*{y})+{y})")))?;
+        let s = self.detachment_reuse(self.last(), q2)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        let s = self.transitivity_reuse(s, q3)?;
+        let s = self.transitivity_reuse(s, m9)?;
+        self.transitivity(s, t6)?;
+        self.implication()?;
+        self.generalization(self.last(), y)?;
+        self.induction(y, base, self.last())?;
+        self.generalize_all(&[x])
+    }
+
+    /// Ax:Ay:Az:[x=y>(z*x)=(z*y)]
+    pub(crate) fn product_congruence_right_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:A{z}:[{x}={y}>({z}*{x})=({z}*{y})]"))
+        {
+            return Ok(pos);
+        }
+        let (xt, yt, zt) = (&term(x), &term(y), &term(z));
+        let (zx, zy) = (&Term::prod(zt, xt), &Term::prod(zt, yt));
+        let v = self.fresh_vars(&[xt, yt, zt], 3);
+        let zero_product = self.zero_product_lemma(&v[0])?;
+        let succ_product = self.succ_product_lemma(&v[0], &v[1])?;
+        let left = self.sum_congruence_left_lemma(&v[0], &v[1], &v[2])?;
+        let right = self.sum_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+
+        // (0*x)=0=(0*y)
+        let p1 = self.instance(zero_product, &v, &[xt])?;
+        let p2 = self.instance(zero_product, &v, &[yt])?;
+        let p3 = self.symmetry_reuse(p2)?;
+        self.supposition(formula(&format!("{x}={y}")))?;
+        self.transitivity(p1, p3)?;
+        self.implication()?;
+        let base = self.last();
+
+        // (Sz*x)=((z*x)+x) and ((z*y)+y)=(Sz*y)
+        let q1 = self.instance(succ_product, &v, &[zt, xt])?;
+        let q2 = self.instance(succ_product, &v, &[zt, yt])?;
+        let q3 = self.symmetry_reuse(q2)?;
+        let q4 = self.instance(left, &v, &[zx, zy, xt])?;
+        let q5 = self.instance(right, &v, &[xt, yt, zy])?;
+
+        self.supposition(formula(&format!("[{x}={y}>({z}*{x})=({z}*{y})]")))?;
+        let hyp = self.last();
+        self.supposition(formula(&format!("{x}={y}")))?;
+        let premise = self.last();
+        let s = self.detachment_reuse(premise, hyp)?;
+        let s = self.detachment_reuse(s, q4)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        let r = self.detachment_reuse(premise, q5)?;
+        let s = self.transitivity_reuse(s, r)?;
+        self.transitivity(s, q3)?;
+        self.implication()?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:Ay:Az:[x=y>(x*z)=(y*z)]
+    pub(crate) fn product_congruence_left_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:A{z}:[{x}={y}>({x}*{z})=({y}*{z})]"))
+        {
+            return Ok(pos);
+        }
+        let (xt, yt, zt) = (&term(x), &term(y), &term(z));
+        let (xz, yz) = (&Term::prod(xt, zt), &Term::prod(yt, zt));
+        let v = self.fresh_vars(&[xt, yt, zt], 3);
+        let left = self.sum_congruence_left_lemma(&v[0], &v[1], &v[2])?;
+        let right = self.sum_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+
+        // (x*0)=0=(y*0)
+        let p1 = self.specify_all(ax3, &[("a", xt)])?;
+        let p2 = self.specify_all(ax3, &[("a", yt)])?;
+        let p3 = self.symmetry_reuse(p2)?;
+        self.supposition(formula(&format!("{x}={y}")))?;
+        self.transitivity(p1, p3)?;
+        self.implication()?;
+        let base = self.last();
+
+        // (x*Sz)=((x*z)+x) and ((y*z)+y)=(y*Sz)
+        let q1 = self.specify_all(ax4, &[("a", xt), ("b", zt)])?;
+        let q2 = self.specify_all(ax4, &[("a", yt), ("b", zt)])?;
+        let q3 = self.symmetry_reuse(q2)?;
+        let q4 = self.instance(left, &v, &[xz, yz, xt])?;
+        let q5 = self.instance(right, &v, &[xt, yt, yz])?;
+
+        self.supposition(formula(&format!("[{x}={y}>({x}*{z})=({y}*{z})]")))?;
+        let hyp = self.last();
+        self.supposition(formula(&format!("{x}={y}")))?;
+        let premise = self.last();
+        let s = self.detachment_reuse(premise, hyp)?;
+        let s = self.detachment_reuse(s, q4)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        let r = self.detachment_reuse(premise, q5)?;
+        let s = self.transitivity_reuse(s, r)?;
+        self.transitivity(s, q3)?;
+        self.implication()?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:(x*S0)=x
+    pub(crate) fn times_one_lemma(&mut self, x: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:({x}*S0)={x}")) {
+            return Ok(pos);
+        }
+        let (zero, xt) = (&Term::zero(), &term(x));
+        let v = self.fresh_vars(&[xt], 1);
+        let zero_sum = self.zero_sum_lemma(&v[0])?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+
+        // (x*S0)=((x*0)+x)=(0+x)=x
+        let p1 = self.specify_all(ax4, &[("a", xt), ("b", zero)])?;
+        let p2 = self.specify_all(ax3, &[("a", xt)])?;
+        let p3 = self.sum_congruence_left(p2, xt)?;
+        let p4 = self.instance(zero_sum, &v, &[xt])?;
+        let s = self.transitivity_reuse(p1, p3)?;
+        let s = self.transitivity_reuse(s, p4)?;
+        self.generalization(s, x)?;
+        Ok(self.last())
+    }
+
+    /// Ax:(S0*x)=x
+    pub(crate) fn one_times_lemma(&mut self, x: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:(S0*{x})={x}")) {
+            return Ok(pos);
+        }
+        let (zero, xt) = (&Term::zero(), &term(x));
+        let v = self.fresh_vars(&[xt], 2);
+        let zero_sum = self.zero_sum_lemma(&v[0])?;
+        let zero_product = self.zero_product_lemma(&v[0])?;
+        let succ_product = self.succ_product_lemma(&v[0], &v[1])?;
+
+        // (S0*x)=((0*x)+x)=(0+x)=x
+        let p1 = self.instance(succ_product, &v, &[zero, xt])?;
+        let p2 = self.instance(zero_product, &v, &[xt])?;
+        let p3 = self.sum_congruence_left(p2, xt)?;
+        let p4 = self.instance(zero_sum, &v, &[xt])?;
+        let s = self.transitivity_reuse(p1, p3)?;
+        let s = self.transitivity_reuse(s, p4)?;
+        self.generalization(s, x)?;
+        Ok(self.last())
+    }
+
+    /// Ax:Ay:(x*y)=(y*x)
+    pub(crate) fn product_commutes_lemma(&mut self, x: &str, y: &str) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:({x}*{y})=({y}*{x})")) {
+            return Ok(pos);
+        }
+        let (xt, yt) = (&term(x), &term(y));
+        let (xy, yx) = (&Term::prod(xt, yt), &Term::prod(yt, xt));
+        let v = self.fresh_vars(&[xt, yt], 3);
+        let zero_product = self.zero_product_lemma(&v[0])?;
+        let succ_product = self.succ_product_lemma(&v[0], &v[1])?;
+        let left = self.sum_congruence_left_lemma(&v[0], &v[1], &v[2])?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+
+        // (x*0)=0=(0*x)
+        let p1 = self.specify_all(ax3, &[("a", xt)])?;
+        let p2 = self.instance(zero_product, &v, &[xt])?;
+        let p3 = self.symmetry_reuse(p2)?;
+        let base = self.transitivity_reuse(p1, p3)?;
+
+        // (x*Sy)=((x*y)+x) and ((y*x)+x)=(Sy*x)
+        let q1 = self.specify_all(ax4, &[("a", xt), ("b", yt)])?;
+        let q2 = self.instance(succ_product, &v, &[yt, xt])?;
+        let q3 = self.symmetry_reuse(q2)?;
+        let q4 = self.instance(left, &v, &[xy, yx, xt])?;
+
+        self.supposition(formula(&format!("({x}*{y})=({y}*{x})")))?;
+        let s = self.detachment_reuse(self.last(), q4)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q3)?;
+        self.implication()?;
+        self.generalization(self.last(), y)?;
+        self.induction(y, base, self.last())?;
+        self.generalize_all(&[x])
+    }
+
+    /// Ax:Ay:Az:(x*(y+z))=((x*y)+(x*z))
+    pub(crate) fn product_distributes_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!(
+            "A{x}:A{y}:A{z}:({x}*({y}+{z}))=(({x}*{y})+({x}*{z}))"
+        )) {
+            return Ok(pos);
+        }
+        let (zero, xt, yt, zt) = (&Term::zero(), &term(x), &term(y), &term(z));
+        let (xy, xz, yz) = (&Term::prod(xt, yt), &Term::prod(xt, zt), &Term::sum(yt, zt));
+        let v = self.fresh_vars(&[xt, yt, zt], 3);
+        let left = self.sum_congruence_left_lemma(&v[0], &v[1], &v[2])?;
+        let right = self.sum_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+        let assoc = self.sum_associates_lemma(&v[0], &v[1], &v[2])?;
+        let times = self.product_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+
+        // (x*(y+0))=(x*y)=((x*y)+(x*0))
+        let p1 = self.specify_all(ax1, &[("a", yt)])?;
+        let p2 = self.instance(times, &v, &[&Term::sum(yt, zero), yt, xt])?;
+        let p2 = self.detachment_reuse(p1, p2)?;
+        let p3 = self.specify_all(ax3, &[("a", xt)])?;
+        let p4 = self.instance(right, &v, &[&Term::prod(xt, zero), zero, xy])?;
+        let p4 = self.detachment_reuse(p3, p4)?;
+        let p5 = self.specify_all(ax1, &[("a", xy)])?;
+        let p6 = self.transitivity_reuse(p4, p5)?;
+        let p7 = self.symmetry_reuse(p6)?;
+        let base = self.transitivity_reuse(p2, p7)?;
+
+        // (x*(y+Sz))=(x*S(y+z))=((x*(y+z))+x)
+        let q1 = self.specify_all(ax2, &[("a", yt), ("b", zt)])?;
+        let q2 = self.instance(
+            times,
+            &v,
+            &[&Term::sum(yt, &Term::succ(zt)), &Term::succ(yz), xt],
+        )?;
+        let q2 = self.detachment_reuse(q1, q2)?;
+        let q3 = self.specify_all(ax4, &[("a", xt), ("b", yz)])?;
+        let q4 = self.transitivity_reuse(q2, q3)?;
+
+        // (((x*y)+(x*z))+x)=((x*y)+((x*z)+x))=((x*y)+(x*Sz))
+        let q5 = self.instance(assoc, &v, &[xy, xz, xt])?;
+        let q6 = self.specify_all(ax4, &[("a", xt), ("b", zt)])?;
+        let q7 = self.symmetry_reuse(q6)?;
+        let q8 = self.instance(
+            right,
+            &v,
+            &[&Term::sum(xz, xt), &Term::prod(xt, &Term::succ(zt)), xy],
+        )?;
+        let q8 = self.detachment_reuse(q7, q8)?;
+        let q9 = self.transitivity_reuse(q5, q8)?;
+        let q10 = self.instance(left, &v, &[&Term::prod(xt, yz), &Term::sum(xy, xz), xt])?;
+
+        self.supposition(formula(&format!("({x}*({y}+{z}))=(({x}*{y})+({x}*{z}))")))?;
+        let s = self.detachment_reuse(self.last(), q10)?;
+        let s = self.transitivity_reuse(q4, s)?;
+        self.transitivity(s, q9)?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:Ay:Az:((x*y)*z)=(x*(y*z))
+    pub(crate) fn product_associates_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) =
+            self.lemma_goal(&format!("A{x}:A{y}:A{z}:(({x}*{y})*{z})=({x}*({y}*{z}))"))
+        {
+            return Ok(pos);
+        }
+        let (zero, xt, yt, zt) = (&Term::zero(), &term(x), &term(y), &term(z));
+        let (xy, yz) = (&Term::prod(xt, yt), &Term::prod(yt, zt));
+        let v = self.fresh_vars(&[xt, yt, zt], 3);
+        let dist = self.product_distributes_lemma(&v[0], &v[1], &v[2])?;
+        let left = self.sum_congruence_left_lemma(&v[0], &v[1], &v[2])?;
+        let times = self.product_congruence_right_lemma(&v[0], &v[1], &v[2])?;
+        let ax3 = self.peano_axiom(3)?;
+        let ax4 = self.peano_axiom(4)?;
+
+        // ((x*y)*0)=0=(x*0)=(x*(y*0))
+        let p1 = self.specify_all(ax3, &[("a", xy)])?;
+        let p2 = self.specify_all(ax3, &[("a", yt)])?;
+        let p3 = self.instance(times, &v, &[&Term::prod(yt, zero), zero, xt])?;
+        let p3 = self.detachment_reuse(p2, p3)?;
+        let p4 = self.specify_all(ax3, &[("a", xt)])?;
+        let p5 = self.transitivity_reuse(p3, p4)?;
+        let p6 = self.symmetry_reuse(p5)?;
+        let base = self.transitivity_reuse(p1, p6)?;
+
+        // ((x*y)*Sz)=(((x*y)*z)+(x*y)) and ((x*(y*z))+(x*y))=(x*((y*z)+y))=(x*(y*Sz))
+        let q1 = self.specify_all(ax4, &[("a", xy), ("b", zt)])?;
+        let q2 = self.instance(left, &v, &[&Term::prod(xy, zt), &Term::prod(xt, yz), xy])?;
+        let q3 = self.instance(dist, &v, &[xt, yz, yt])?;
+        let q4 = self.symmetry_reuse(q3)?;
+        let q5 = self.specify_all(ax4, &[("a", yt), ("b", zt)])?;
+        let q6 = self.symmetry_reuse(q5)?;
+        let q7 = self.instance(
+            times,
+            &v,
+            &[&Term::sum(yz, yt), &Term::prod(yt, &Term::succ(zt)), xt],
+        )?;
+        let q7 = self.detachment_reuse(q6, q7)?;
+        let q8 = self.transitivity_reuse(q4, q7)?;
+
+        self.supposition(formula(&format!("(({x}*{y})*{z})=({x}*({y}*{z}))")))?;
+        let s = self.detachment_reuse(self.last(), q2)?;
+        let s = self.transitivity_reuse(q1, s)?;
+        self.transitivity(s, q8)?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    /// Ax:Ay:Az:[(x+z)=(y+z)>x=y]
+    pub(crate) fn sum_cancels_lemma(
+        &mut self,
+        x: &str,
+        y: &str,
+        z: &str,
+    ) -> Result<usize, LogicError> {
+        if let Some(pos) = self.lemma_goal(&format!("A{x}:A{y}:A{z}:[({x}+{z})=({y}+{z})>{x}={y}]"))
+        {
+            return Ok(pos);
+        }
+        let (xt, yt, zt) = (&term(x), &term(y), &term(z));
+        let ax1 = self.peano_axiom(1)?;
+        let ax2 = self.peano_axiom(2)?;
+
+        // x=(x+0) and (y+0)=y
+        let p1 = self.specify_all(ax1, &[("a", xt)])?;
+        let p2 = self.symmetry_reuse(p1)?;
+        let p3 = self.specify_all(ax1, &[("a", yt)])?;
+        self.supposition(formula(&format!("({x}+0)=({y}+0)")))?;
+        let s = self.transitivity_reuse(p2, self.last())?;
+        self.transitivity(s, p3)?;
+        self.implication()?;
+        let base = self.last();
+
+        // S(x+z)=(x+Sz) and (y+Sz)=S(y+z)
+        let q1 = self.specify_all(ax2, &[("a", xt), ("b", zt)])?;
+        let q2 = self.symmetry_reuse(q1)?;
+        let q3 = self.specify_all(ax2, &[("a", yt), ("b", zt)])?;
+
+        self.supposition(formula(&format!("[({x}+{z})=({y}+{z})>{x}={y}]")))?;
+        let hyp = self.last();
+        self.supposition(formula(&format!("({x}+S{z})=({y}+S{z})")))?;
+        let s = self.transitivity_reuse(q2, self.last())?;
+        let s = self.transitivity_reuse(s, q3)?;
+        self.predecessor(s)?;
+        self.detachment(self.last(), hyp)?;
+        self.implication()?;
+        self.implication()?;
+        self.generalization(self.last(), z)?;
+        self.induction(z, base, self.last())?;
+        self.generalize_all(&[x, y])
+    }
+
+    // Given theorem n of the form s=t detach the instance of a congruence lemma for s, t and r.
+    pub(crate) fn congruence(
+        &mut self,
+        n: usize,
+        r: &Term,
+        lemma: fn(&mut Deduction, &str, &str, &str) -> Result<usize, LogicError>,
+    ) -> Result<usize, LogicError> {
+        let (s, t) = equality_sides(self.get_theorem(n)?)?;
+        let v = self.fresh_vars(&[&s, &t, r], 3);
+        let lemma = lemma(self, &v[0], &v[1], &v[2])?;
+        let inst = self.instance(lemma, &v, &[&s, &t, r])?;
+        self.detachment_reuse(n, inst)
+    }
+}
+
+pub(crate) fn equality_sides(formula: &Formula) -> Result<(Term, Term), LogicError> {
+    match formula {
+        Formula::Equality(s, t) => Ok((s.clone(), t.clone())),
+        _ => Err(LogicError(format!(
+            "Congruence Error: {formula} is not a Formula::Equality"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_lemmas() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Lemmas");
+        let n = d.sum_commutes_lemma("c", "d")?;
+        assert_eq!(d.theorem(n).formula().to_string(), "Ac:Ad:(c+d)=(d+c)");
+        let n = d.product_congruence_right_lemma("c", "d", "e")?;
+        assert_eq!(
+            d.theorem(n).formula().to_string(),
+            "Ac:Ad:Ae:[c=d>(e*c)=(e*d)]"
+        );
+        let n = d.product_congruence_left_lemma("c", "d", "e")?;
+        assert_eq!(
+            d.theorem(n).formula().to_string(),
+            "Ac:Ad:Ae:[c=d>(c*e)=(d*e)]"
+        );
+        assert_eq!(d.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fresh_vars() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Fresh");
+        d.supposition(Formula::try_from("c=d").unwrap())?;
+        let t = Term::try_from("(e+f')").unwrap();
+        assert_eq!(d.fresh_vars(&[&t], 3), vec!["f", "g", "h"]);
+        Ok(())
+    }
+}
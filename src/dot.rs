@@ -0,0 +1,174 @@
+//! Graphviz graphs of the dependencies between the lines of a Deduction.
+//!
+//! Every theorem is a node labelled with its Formula and the rule that produced it, with an edge from each theorem it was derived from. Each supposition is drawn as a cluster holding the lines derived inside it. Lines that the last line of the Deduction does not depend on are shaded so dead ends in a long proof stand out.
+//! ```
+//! # use tnt::{Deduction, Term};
+//! let mut d = Deduction::new("One Plus Zero");
+//! d.add_axiom(1).unwrap();
+//! d.add_axiom(0).unwrap();
+//! d.specification(0, "a", &Term::one()).unwrap();
+//! let dot = d.to_dot();
+//! assert!(dot.contains("  n0 -> n2;\n"));
+//! assert!(dot.contains("  n1 [label=\"1) Aa:~Sa=0\\naxiom\", style=\"filled,dashed\", fillcolor=\"#eeeeee\"];\n"));
+//! ```
+
+use std::fs::File;
+use std::io::{Error, Write};
+
+use crate::deduction::ScopeEvent;
+use crate::Deduction;
+
+// Escape a string for use inside double quotes in DOT.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Deduction {
+    // Mark every line that the last line depends on.
+    fn used_lines(&self) -> Vec<bool> {
+        let mut used = vec![false; self.theorems().len()];
+        if let Some(last) = used.last_mut() {
+            *last = true;
+        }
+        // Premises always come before the lines derived from them
+        for t in self.theorems().rev() {
+            if used[t.position] {
+                for p in t.justification.premises() {
+                    used[p] = true;
+                }
+            }
+        }
+        used
+    }
+
+    /// A graph of the Deduction in the DOT language of Graphviz, as described in the module documentation.
+    pub fn to_dot(&self) -> String {
+        let used = self.used_lines();
+        let mut out = format!("digraph \"{}\" {{\n", escape(&self.title));
+        out.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+
+        for event in self.scope_events() {
+            let t = match event {
+                ScopeEvent::Open(t) => {
+                    let indent = "  ".repeat(t.depth + 1);
+                    let outer = "  ".repeat(t.depth);
+                    out.push_str(&format!("{outer}subgraph cluster_{} {{\n", t.position));
+                    out.push_str(&format!(
+                        "{indent}label=\"supposition {}\";\n{indent}style=rounded;\n",
+                        t.position
+                    ));
+                    continue;
+                }
+                ScopeEvent::Close(d) => {
+                    out.push_str(&format!("{}}}\n", "  ".repeat(d + 1)));
+                    continue;
+                }
+                ScopeEvent::Line(t) => t,
+            };
+            let indent = "  ".repeat(t.depth + 1);
+            let label = escape(&format!(
+                "{}) {}\n{}",
+                t.position,
+                t.formula(),
+                t.justification.rule()
+            ))
+            .replace('\n', "\\n");
+            if used[t.position] {
+                out.push_str(&format!("{indent}n{} [label=\"{label}\"];\n", t.position));
+            } else {
+                out.push_str(&format!(
+                    "{indent}n{} [label=\"{label}\", style=\"filled,dashed\", fillcolor=\"#eeeeee\"];\n",
+                    t.position
+                ));
+            }
+        }
+
+        for t in self.theorems() {
+            for p in t.justification.premises() {
+                out.push_str(&format!("  n{p} -> n{};\n", t.position));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Create a DOT file with the given file name that holds the graph given by to_dot.
+    pub fn dot_file(&self, filename: &str) -> Result<(), Error> {
+        let filename = format!("{}.dot", filename);
+        let mut file = File::create(filename)?;
+        file.write_all(self.to_dot().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{Formula, LogicError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_dot() -> Result<(), LogicError> {
+        let mut d = Deduction::new("A \"Nested\" Fantasy");
+        d.add_axiom(1)?;
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.supposition(Formula::try_from("b=0")?)?;
+        d.symmetry(2)?;
+        d.implication()?;
+        d.implication()?;
+        let expected = "\
+digraph \"A \\\"Nested\\\" Fantasy\" {
+  node [shape=box, fontname=\"monospace\"];
+  n0 [label=\"0) Aa:(a+0)=a\\naxiom\", style=\"filled,dashed\", fillcolor=\"#eeeeee\"];
+  subgraph cluster_1 {
+    label=\"supposition 1\";
+    style=rounded;
+    n1 [label=\"1) a=0\\nsupposition\"];
+    subgraph cluster_2 {
+      label=\"supposition 2\";
+      style=rounded;
+      n2 [label=\"2) b=0\\nsupposition\"];
+      n3 [label=\"3) 0=b\\nsymmetry\"];
+    }
+    n4 [label=\"4) [b=0>0=b]\\nimplication\"];
+  }
+  n5 [label=\"5) [a=0>[b=0>0=b]]\\nimplication\"];
+  n2 -> n3;
+  n2 -> n4;
+  n3 -> n4;
+  n1 -> n5;
+  n4 -> n5;
+}
+";
+        assert_eq!(d.to_dot(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dot_open() -> Result<(), LogicError> {
+        // suppositions that are never discharged are still closed
+        let mut d = Deduction::new("Open");
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.supposition(Formula::try_from("b=0")?)?;
+        d.symmetry(1)?;
+        let expected = "\
+digraph \"Open\" {
+  node [shape=box, fontname=\"monospace\"];
+  subgraph cluster_0 {
+    label=\"supposition 0\";
+    style=rounded;
+    n0 [label=\"0) a=0\\nsupposition\", style=\"filled,dashed\", fillcolor=\"#eeeeee\"];
+    subgraph cluster_1 {
+      label=\"supposition 1\";
+      style=rounded;
+      n1 [label=\"1) b=0\\nsupposition\"];
+      n2 [label=\"2) 0=b\\nsymmetry\"];
+    }
+  }
+  n1 -> n2;
+}
+";
+        assert_eq!(d.to_dot(), expected);
+        Ok(())
+    }
+}
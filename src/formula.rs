@@ -1,3 +1,4 @@
+use crate::numbering::{ByteNumbering, GodelNumbering};
 use crate::parsing::parser::string_to_formula;
 use crate::term::VARIABLE_NAME;
 use crate::{LogicError, Term};
@@ -67,13 +68,43 @@ impl Formula {
             ),
             Self::Or(l, r) => format!("\\langle {} \\vee {} \\rangle", l.to_latex(), r.to_latex()),
             Self::Implies(l, r) => format!(
-                " \\langle{} \\rhsarrow {} \\rangle",
+                "\\langle {} \\supset {} \\rangle",
                 l.to_latex(),
                 r.to_latex()
             ),
         }
     }
 
+    /// Return a String formatting the Formula as MathML with Hofstadter's original notation, without the enclosing math element.
+    pub fn to_mathml(&self) -> String {
+        let binary = |l: &Formula, op: &str, r: &Formula| {
+            format!(
+                "<mrow><mo>⟨</mo>{}<mo>{op}</mo>{}<mo>⟩</mo></mrow>",
+                l.to_mathml(),
+                r.to_mathml()
+            )
+        };
+        match self {
+            Self::Equality(l, r) => {
+                format!("<mrow>{}<mo>=</mo>{}</mrow>", l.to_mathml(), r.to_mathml())
+            }
+            Self::Universal(var, inner) => format!(
+                "<mrow><mo>∀</mo>{}<mo>:</mo>{}</mrow>",
+                Term::Variable(var.clone()).to_mathml(),
+                inner.to_mathml()
+            ),
+            Self::Existential(var, inner) => format!(
+                "<mrow><mo>∃</mo>{}<mo>:</mo>{}</mrow>",
+                Term::Variable(var.clone()).to_mathml(),
+                inner.to_mathml()
+            ),
+            Self::Negation(inner) => format!("<mrow><mo>¬</mo>{}</mrow>", inner.to_mathml()),
+            Self::And(l, r) => binary(l, "∧", r),
+            Self::Or(l, r) => binary(l, "∨", r),
+            Self::Implies(l, r) => binary(l, "⊃", r),
+        }
+    }
+
     /// Eliminate all universal quantification of some Variable and then replace all instances of that variable with the provided Term
     pub fn specify<S: ToString>(&mut self, var_name: &S, term: &Term) {
         match self {
@@ -169,6 +200,106 @@ impl Formula {
         }
     }
 
+    /// The Term found by following a path from this Formula. Each step of the path picks a child: 0 for the inner Formula of a quantification or Negation and 0 or 1 for the left or right side of an Equality, And, Or or Implies. Once the path reaches a Term it continues as described by Term::subterm.
+    /// ```
+    /// # use tnt::{Formula, Term};
+    /// # use std::convert::TryFrom;
+    /// let f = Formula::try_from("Aa:[a=b>~S(a+b)=0]").unwrap();
+    /// assert_eq!(f.term_at(&[0, 1, 0, 0, 0, 1]), Some(&Term::var("b")));
+    /// assert_eq!(f.term_at(&[0, 1]), None);
+    /// ```
+    pub fn term_at(&self, path: &[usize]) -> Option<&Term> {
+        match (self, path) {
+            (Self::Equality(lhs, _), [0, rest @ ..]) => lhs.subterm(rest),
+            (Self::Equality(_, rhs), [1, rest @ ..]) => rhs.subterm(rest),
+            (
+                Self::Universal(_, inner) | Self::Existential(_, inner) | Self::Negation(inner),
+                [0, rest @ ..],
+            ) => inner.term_at(rest),
+            (Self::And(lhs, _) | Self::Or(lhs, _) | Self::Implies(lhs, _), [0, rest @ ..]) => {
+                lhs.term_at(rest)
+            }
+            (Self::And(_, rhs) | Self::Or(_, rhs) | Self::Implies(_, rhs), [1, rest @ ..]) => {
+                rhs.term_at(rest)
+            }
+            _ => None,
+        }
+    }
+
+    // Mutable version of term_at.
+    pub(crate) fn term_at_mut(&mut self, path: &[usize]) -> Option<&mut Term> {
+        match (self, path) {
+            (Self::Equality(lhs, _), [0, rest @ ..]) => lhs.subterm_mut(rest),
+            (Self::Equality(_, rhs), [1, rest @ ..]) => rhs.subterm_mut(rest),
+            (
+                Self::Universal(_, inner) | Self::Existential(_, inner) | Self::Negation(inner),
+                [0, rest @ ..],
+            ) => inner.term_at_mut(rest),
+            (Self::And(lhs, _) | Self::Or(lhs, _) | Self::Implies(lhs, _), [0, rest @ ..]) => {
+                lhs.term_at_mut(rest)
+            }
+            (Self::And(_, rhs) | Self::Or(_, rhs) | Self::Implies(_, rhs), [1, rest @ ..]) => {
+                rhs.term_at_mut(rest)
+            }
+            _ => None,
+        }
+    }
+
+    // The names of the variables quantified by the Formulas that a path passes through.
+    pub(crate) fn bound_along(&self, path: &[usize]) -> IndexSet<String> {
+        let mut out = IndexSet::new();
+        let mut formula = self;
+        for step in path {
+            formula = match (formula, step) {
+                (Self::Universal(v, inner) | Self::Existential(v, inner), 0) => {
+                    out.insert(v.clone());
+                    inner
+                }
+                (Self::Negation(inner), 0) => inner,
+                (Self::And(lhs, _) | Self::Or(lhs, _) | Self::Implies(lhs, _), 0) => lhs,
+                (Self::And(_, rhs) | Self::Or(_, rhs) | Self::Implies(_, rhs), 1) => rhs,
+                _ => break,
+            };
+        }
+        out
+    }
+
+    /// The path of every occurrence of a Term in the Formula, in the form used by Formula::term_at.
+    /// ```
+    /// # use tnt::{Formula, Term};
+    /// # use std::convert::TryFrom;
+    /// let f = Formula::try_from("(a+Sa)=S(a*b)").unwrap();
+    /// assert_eq!(f.paths_to(&Term::var("a")), vec![vec![0, 0], vec![0, 1, 0], vec![1, 0, 0]]);
+    /// ```
+    pub fn paths_to(&self, term: &Term) -> Vec<Vec<usize>> {
+        fn walk(f: &Formula, term: &Term, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+            let children: Vec<&Formula> = match f {
+                Formula::Equality(lhs, rhs) => {
+                    for (i, side) in [lhs, rhs].iter().enumerate() {
+                        prefix.push(i);
+                        side.paths_to(term, prefix, out);
+                        prefix.pop();
+                    }
+                    vec![]
+                }
+                Formula::Universal(_, inner)
+                | Formula::Existential(_, inner)
+                | Formula::Negation(inner) => vec![inner],
+                Formula::And(lhs, rhs) | Formula::Or(lhs, rhs) | Formula::Implies(lhs, rhs) => {
+                    vec![lhs, rhs]
+                }
+            };
+            for (i, child) in children.into_iter().enumerate() {
+                prefix.push(i);
+                walk(child, term, prefix, out);
+                prefix.pop();
+            }
+        }
+        let mut out = Vec::new();
+        walk(self, term, &mut Vec::new(), &mut out);
+        out
+    }
+
     /// Fills the provided IndexSet with the name of every variable in the Formula.
     pub fn get_vars(&self, var_names: &mut IndexSet<String>) {
         match self {
@@ -197,7 +328,7 @@ impl Formula {
         let mut all_v = IndexSet::<String>::new();
         let mut bound_v = IndexSet::<String>::new();
         self.get_vars(&mut all_v);
-        self.get_vars(&mut bound_v);
+        self.get_vars_bound(&mut bound_v);
         for free_v in all_v.difference(&bound_v) {
             var_names.insert(free_v.clone());
         }
@@ -310,6 +441,7 @@ impl Formula {
     }
 
     /// As .austere() but mutates the Formula instead.
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_austere(&mut self) {
         let vars = {
             let mut v = IndexSet::new();
@@ -319,6 +451,7 @@ impl Formula {
         self.to_austere_with(&vars);
     }
 
+    #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_austere_with(&mut self, vars: &IndexSet<String>) {
         let mut mask = String::from("#");
         for v in vars.iter() {
@@ -336,8 +469,35 @@ impl Formula {
 
     /// Create the unique BigUint that characterizes the Formula. This is done by converting the Formula to its austere form and then reading the bytes as a bigendian number.
     pub fn arithmetize(&self) -> BigUint {
-        let s = self.austere().to_string();
-        BigUint::from_bytes_be(s.as_bytes())
+        self.arithmetize_with(&ByteNumbering)
+            .expect("the byte numbering can encode any string")
+    }
+
+    /// Create the unique BigUint that characterizes the austere form of the Formula in the given numbering, or an error if the numbering cannot encode it.
+    pub fn arithmetize_with(&self, numbering: &impl GodelNumbering) -> Result<BigUint, LogicError> {
+        numbering.encode(&self.austere().to_string())
+    }
+
+    /// Recover the austere Formula characterized by a BigUint in the given numbering.
+    /// ```
+    /// # use tnt::Formula;
+    /// # use tnt::numbering::CodonNumbering;
+    /// # use std::convert::TryFrom;
+    /// let f = Formula::try_from("Ab:~Sb=0").unwrap();
+    /// let n = f.arithmetize_with(&CodonNumbering).unwrap();
+    /// assert_eq!(Formula::from_arithmetized(&n, &CodonNumbering).unwrap(), f.austere());
+    /// ```
+    pub fn from_arithmetized(
+        number: &BigUint,
+        numbering: &impl GodelNumbering,
+    ) -> Result<Formula, LogicError> {
+        let s = numbering.decode(number)?;
+        match Formula::try_from(s.as_str()) {
+            Ok(f) if f.to_string() == s => Ok(f),
+            _ => Err(LogicError(format!(
+                "Numbering Error: `{s}` is not a well formed Formula"
+            ))),
+        }
     }
 
     // These are guaranteed to produce well-formed formulas of TNT. However they may produce false statements.
@@ -473,6 +633,14 @@ mod test {
         assert_eq!(t0, t1);
     }
 
+    #[test]
+    fn free_vars() {
+        let f = Formula::try_from("Ea:[a=b&Ac:c=Sd]").unwrap();
+        let mut vars = IndexSet::new();
+        f.get_vars_free(&mut vars);
+        assert_eq!(vars.into_iter().collect::<Vec<_>>(), vec!["b", "d"]);
+    }
+
     #[test]
     fn from_big_uint() {
         let t0 = Formula::try_from("Ea:Aa':[a'=a'&a=Sa'']").unwrap();
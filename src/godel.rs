@@ -0,0 +1,399 @@
+//! Arithmoquinification and the construction of Gödel's sentence G as described in Gödel, Escher, Bach.
+//!
+//! To arithmoquine a Formula with a free variable is to replace that variable with the numeral of the Formula's own Gödel number. The uncle of G says that no number is a proof of the arithmoquinification of the number a''
+//! ```text
+//! ~Ea:Ea':[PROOF-PAIR{a,a'}&ARITHMOQUINE{a'',a'}]
+//! ```
+//! and G is the arithmoquinification of the uncle, which then says that G itself has no proof.
+//!
+//! The relation that PROOF-PAIR expresses can be decided directly on numbers with `is_proof_pair`.
+
+use indexmap::IndexSet;
+use num::{BigUint, Integer, ToPrimitive, Zero};
+
+use crate::check::SearchError;
+use crate::derivations::fresh_names;
+use crate::numbering::{ByteNumbering, CodonNumbering, GodelNumbering};
+use crate::{Deduction, Formula, LogicError, Term};
+
+/// How the Gödel number of a Formula is written as a Term.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Numeral {
+    /// The number as a chain of successors of zero, such as SSS0. Since the Gödel numbers of Formulas are enormous this is only possible for numbers up to `Numeral::MAX_SUCCESSORS`.
+    Successor,
+    /// The number written in base ten as a Term that evaluates to it, such as ((SS0*SSSSSSSSSS0)+SSS0) for 23.
+    Sugared,
+}
+
+impl Numeral {
+    /// The largest number that can be written as a chain of successors.
+    pub const MAX_SUCCESSORS: usize = 10_000;
+
+    /// A Term whose value is the given number.
+    /// ```
+    /// # use tnt::godel::Numeral;
+    /// # use num::BigUint;
+    /// let n = BigUint::from(203u32);
+    /// let t = Numeral::Sugared.term(&n).unwrap();
+    /// assert_eq!(t.to_string(), "(((SS0*SSSSSSSSSS0)*SSSSSSSSSS0)+SSS0)");
+    /// ```
+    pub fn term(&self, n: &BigUint) -> Result<Term, LogicError> {
+        match self {
+            Numeral::Successor => match n.to_usize() {
+                Some(k) if k <= Self::MAX_SUCCESSORS => Ok(Term::numeral(k)),
+                _ => Err(LogicError(format!(
+                    "Numeral Error: {n} is too large to write as a chain of successors"
+                ))),
+            },
+            Numeral::Sugared => {
+                let ten = BigUint::from(10u32);
+                let mut digits = Vec::new();
+                let mut rest = n.clone();
+                while !rest.is_zero() {
+                    let (q, r) = rest.div_rem(&ten);
+                    digits.push(r.to_usize().unwrap());
+                    rest = q;
+                }
+                // Horner's method from the most significant digit
+                let mut out: Option<Term> = None;
+                for d in digits.into_iter().rev() {
+                    out = Some(match out {
+                        None => Term::numeral(d),
+                        Some(t) => {
+                            let shifted = Term::prod(&t, &Term::numeral(10));
+                            if d == 0 {
+                                shifted
+                            } else {
+                                Term::sum(&shifted, &Term::numeral(d))
+                            }
+                        }
+                    });
+                }
+                Ok(out.unwrap_or(Term::Zero))
+            }
+        }
+    }
+}
+
+// Check that a variable is free in a Formula and never quantified so it can be replaced everywhere it appears.
+fn free_var(formula: &Formula, var_name: &str) -> Result<(), LogicError> {
+    if formula.contains_var_bound(&var_name) {
+        return Err(LogicError(format!(
+            "Arithmoquine Error: the Term::Variable `{var_name}` is quantified in the Formula `{formula}`"
+        )));
+    }
+    if !formula.contains_var(&var_name) {
+        return Err(LogicError(format!(
+            "Arithmoquine Error: the Term::Variable `{var_name}` does not appear in the Formula `{formula}`"
+        )));
+    }
+    Ok(())
+}
+
+impl Formula {
+    /// Replace the free variable with the sugared numeral of the codon number of the Formula, as in Gödel, Escher, Bach.
+    /// ```
+    /// # use tnt::Formula;
+    /// # use std::convert::TryFrom;
+    /// let f = Formula::try_from("a=S0").unwrap();
+    /// let q = f.arithmoquine("a").unwrap();
+    /// assert!(q.to_string().ends_with("=S0"));
+    /// ```
+    pub fn arithmoquine(&self, var_name: &str) -> Result<Formula, LogicError> {
+        self.arithmoquine_with(var_name, &CodonNumbering, Numeral::Sugared)
+    }
+
+    /// Replace the free variable with the numeral of the Gödel number of the Formula in the given numbering. The variable must not be quantified anywhere in the Formula.
+    pub fn arithmoquine_with(
+        &self,
+        var_name: &str,
+        numbering: &impl GodelNumbering,
+        numeral: Numeral,
+    ) -> Result<Formula, LogicError> {
+        free_var(self, var_name)?;
+        let n = self.arithmetize_with(numbering)?;
+        let mut out = self.clone();
+        out.replace_free(&var_name, &numeral.term(&n)?);
+        Ok(out)
+    }
+}
+
+/// Decide whether m is the number of a valid Deduction whose last line is the Formula numbered a, in the byte numbering used by `arithmetize`. Numbers that do not encode a Deduction or a Formula are never proof pairs.
+///
+/// A number whose Deduction is given up on by the search described for is_proof_pair_with is not taken to be a proof pair.
+/// ```
+/// # use tnt::{Deduction, Term};
+/// # use tnt::godel::is_proof_pair;
+/// let mut d = Deduction::new("One Plus Zero");
+/// d.add_axiom(1).unwrap();
+/// d.specification(0, "a", &Term::one()).unwrap();
+/// let m = d.arithmetize();
+/// let a = d.last_theorem().formula().arithmetize();
+/// assert!(is_proof_pair(&m, &a));
+/// assert!(!is_proof_pair(&a, &m));
+/// ```
+pub fn is_proof_pair(m: &BigUint, a: &BigUint) -> bool {
+    is_proof_pair_with(m, a, &ByteNumbering).unwrap_or(false)
+}
+
+/// Decide whether m is the number of a valid Deduction whose last line is the Formula numbered a in the given numbering. An error is returned if m does not encode a sequence of Formulas, if a does not encode a Formula or if the search below gives up.
+///
+/// The variables of a Deduction are renamed consistently across all of its lines when it is arithmetized so the last line is compared to the Formula in austere form.
+///
+/// Only the Formulas of the Deduction are encoded, so a Justification is searched for on every line along with where each supposition begins and ends. A line that could begin or end a supposition as well as follow from earlier lines is first taken to follow from earlier lines, and the search returns to it to try the other ways when a later line fails. The time taken could multiply with every such line, so the search gives up with an error after returning to earlier lines a fixed number of times. Only the last line is checked before the search begins.
+/// ```
+/// # use tnt::{Deduction, Formula, Term};
+/// # use tnt::godel::is_proof_pair_with;
+/// # use tnt::numbering::CodonNumbering;
+/// # use std::convert::TryFrom;
+/// let mut d = Deduction::new("One Plus Zero");
+/// d.add_axiom(1).unwrap();
+/// d.specification(0, "a", &Term::one()).unwrap();
+/// let m = d.arithmetize_with(&CodonNumbering).unwrap();
+/// let a = Formula::try_from("(S0+0)=S0").unwrap().arithmetize_with(&CodonNumbering).unwrap();
+/// assert!(is_proof_pair_with(&m, &a, &CodonNumbering).unwrap());
+/// let b = Formula::try_from("(S0+0)=0").unwrap().arithmetize_with(&CodonNumbering).unwrap();
+/// assert!(!is_proof_pair_with(&m, &b, &CodonNumbering).unwrap());
+/// ```
+pub fn is_proof_pair_with(
+    m: &BigUint,
+    a: &BigUint,
+    numbering: &impl GodelNumbering,
+) -> Result<bool, LogicError> {
+    let lines = Deduction::arithmetized_lines(m, numbering)?;
+    let theorem = Formula::from_arithmetized(a, numbering)?;
+    match lines.last() {
+        Some(last) if last.austere() == theorem.austere() => {
+            match Deduction::from_austere_lines(&lines) {
+                Ok(_) => Ok(true),
+                Err(SearchError::Invalid(_)) => Ok(false),
+                Err(SearchError::GaveUp(e)) => Err(e),
+            }
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Builds G from a Formula expressing that one number is the Gödel number of a proof of the Formula numbered by another and a Formula expressing that one number is the arithmoquinification of another.
+/// ```
+/// # use tnt::Formula;
+/// # use tnt::godel::GodelSentence;
+/// # use std::convert::TryFrom;
+/// // Stand-ins for the real, very long, Formulas
+/// let proof_pair = Formula::try_from("Ec:(m+c)=t").unwrap();
+/// let arithmoquine = Formula::try_from("(n+n)=r").unwrap();
+/// let g = GodelSentence::new(&proof_pair, "m", "t")
+///     .arithmoquine(&arithmoquine, "n", "r")
+///     .build()
+///     .unwrap();
+/// assert!(g.to_string().starts_with("~Ea:Eb:[Ec:(a+c)=b&("));
+/// ```
+#[derive(Clone, Debug)]
+pub struct GodelSentence {
+    proof_pair: (Formula, String, String),
+    arithmoquine: Option<(Formula, String, String)>,
+    numeral: Numeral,
+}
+
+impl GodelSentence {
+    /// Begin with a Formula that is true exactly when the free variable `proof` is the Gödel number of a proof of the Formula whose Gödel number is the free variable `theorem`.
+    pub fn new(proof_pair: &Formula, proof: &str, theorem: &str) -> Self {
+        GodelSentence {
+            proof_pair: (proof_pair.clone(), proof.to_string(), theorem.to_string()),
+            arithmoquine: None,
+            numeral: Numeral::Sugared,
+        }
+    }
+
+    /// The Formula that is true exactly when the free variable `result` is the Gödel number of the arithmoquinification of the Formula whose Gödel number is the free variable `source`.
+    pub fn arithmoquine(mut self, arithmoquine: &Formula, source: &str, result: &str) -> Self {
+        self.arithmoquine = Some((arithmoquine.clone(), source.to_string(), result.to_string()));
+        self
+    }
+
+    /// How the Gödel number of the uncle is written in G. The default is Numeral::Sugared.
+    pub fn numeral(mut self, numeral: Numeral) -> Self {
+        self.numeral = numeral;
+        self
+    }
+
+    /// The uncle of G, returned along with the name of its free variable. The variables of both Formulas are renamed to ones that neither uses.
+    pub fn uncle(&self) -> Result<(Formula, String), LogicError> {
+        let (aq, source, result) = self.arithmoquine.as_ref().ok_or_else(|| {
+            LogicError("Arithmoquine Error: no ARITHMOQUINE Formula was given".to_string())
+        })?;
+        let (pp, proof, theorem) = &self.proof_pair;
+        for (f, v) in [(pp, proof), (pp, theorem), (aq, source), (aq, result)] {
+            free_var(f, v)?;
+        }
+
+        if proof == theorem || source == result {
+            return Err(LogicError(
+                "Arithmoquine Error: the two free variables of a Formula must have different names"
+                    .to_string(),
+            ));
+        }
+
+        // Three variables that appear in neither Formula
+        let mut used = IndexSet::new();
+        pp.get_vars(&mut used);
+        aq.get_vars(&mut used);
+        let fresh = fresh_names(&used, 3);
+        let (m, t, q) = (&fresh[0], &fresh[1], &fresh[2]);
+
+        let mut pp = pp.clone();
+        pp.rename_var(proof, m);
+        pp.rename_var(theorem, t);
+        let mut aq = aq.clone();
+        aq.rename_var(source, q);
+        aq.rename_var(result, t);
+        let inner = Formula::and(&pp, &aq);
+        let uncle = Formula::not(&Formula::exists(m, &Formula::exists(t, &inner)));
+        Ok((uncle, q.clone()))
+    }
+
+    /// Build G by arithmoquining the uncle using the codon numbering of Gödel, Escher, Bach.
+    pub fn build(&self) -> Result<Formula, LogicError> {
+        self.build_with(&CodonNumbering)
+    }
+
+    /// Build G by arithmoquining the uncle using the given numbering.
+    pub fn build_with(&self, numbering: &impl GodelNumbering) -> Result<Formula, LogicError> {
+        let (uncle, q) = self.uncle()?;
+        uncle.arithmoquine_with(&q, numbering, self.numeral)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::decision::eval_term;
+    use crate::numbering::ByteNumbering;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_numeral() -> Result<(), LogicError> {
+        for n in [0u64, 1, 7, 10, 23, 100, 6_661_116_660] {
+            let n = BigUint::from(n);
+            let t = Numeral::Sugared.term(&n)?;
+            assert_eq!(eval_term(&t, &HashMap::new())?, n);
+        }
+        let t = Numeral::Successor.term(&BigUint::from(3u32))?;
+        assert_eq!(t.to_string(), "SSS0");
+        assert!(Numeral::Successor.term(&BigUint::from(1u32 << 20)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arithmoquine() -> Result<(), LogicError> {
+        let f = Formula::try_from("a=S0")?;
+        let n = f.arithmetize_with(&CodonNumbering)?;
+        assert_eq!(n.to_string(), "262111123666");
+        match f.arithmoquine("a")? {
+            Formula::Equality(l, r) => {
+                assert_eq!(eval_term(&l, &HashMap::new())?, n);
+                assert_eq!(r, Term::one());
+            }
+            _ => panic!("arithmoquining changed the shape of the Formula"),
+        }
+        // the Gödel number is of the austere form of the Formula
+        let g = Formula::try_from("b=0")?;
+        let q = g.arithmoquine_with("b", &ByteNumbering, Numeral::Sugared)?;
+        assert_eq!(
+            q,
+            Formula::try_from("a=0")?.arithmoquine_with("a", &ByteNumbering, Numeral::Sugared)?
+        );
+
+        assert!(f.arithmoquine("b").is_err());
+        assert!(Formula::try_from("Aa:a=a")?.arithmoquine("a").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_godel_sentence() -> Result<(), LogicError> {
+        // uses a, b and c so the uncle must use other variables
+        let proof_pair = Formula::try_from("Ea:(b+a)=c")?;
+        let arithmoquine = Formula::try_from("(a*SS0)=b")?;
+        let builder =
+            GodelSentence::new(&proof_pair, "b", "c").arithmoquine(&arithmoquine, "a", "b");
+        let (uncle, q) = builder.uncle()?;
+        assert_eq!(uncle.to_string(), "~Ed:Ee:[Ea:(d+a)=e&(f*SS0)=e]");
+        assert_eq!(q, "f");
+        let g = builder.build()?;
+        let n = uncle.arithmetize_with(&CodonNumbering)?;
+        let mut expected = uncle.clone();
+        expected.replace_free(&q, &Numeral::Sugared.term(&n)?);
+        assert_eq!(g, expected);
+
+        assert!(GodelSentence::new(&proof_pair, "b", "c").build().is_err());
+        assert!(GodelSentence::new(&proof_pair, "a", "c")
+            .arithmoquine(&arithmoquine, "a", "b")
+            .build()
+            .is_err());
+        // the two free variables of each Formula must be distinct
+        let repeated = Formula::try_from("(a+a)=b")?;
+        assert!(GodelSentence::new(&repeated, "a", "a")
+            .arithmoquine(&arithmoquine, "a", "b")
+            .uncle()
+            .is_err());
+        assert!(GodelSentence::new(&proof_pair, "b", "c")
+            .arithmoquine(&arithmoquine, "b", "b")
+            .uncle()
+            .is_err());
+        // a successor chain for the number of the uncle is far too long
+        assert!(builder.numeral(Numeral::Successor).build().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_proof_pair() -> Result<(), LogicError> {
+        use crate::numbering::PrimePowerNumbering;
+
+        let mut d = Deduction::new("Fantasy");
+        d.supposition(Formula::try_from("b=0")?)?;
+        d.symmetry(0)?;
+        d.implication()?;
+        d.generalization(2, "b")?;
+        let a = Formula::try_from("Ab:[b=0>0=b]")?;
+        let m = d.arithmetize();
+        assert!(is_proof_pair(&m, &a.arithmetize()));
+        // the variable names of the theorem do not matter
+        assert!(is_proof_pair(&m, &a.austere().arithmetize()));
+        // only the last line is the theorem of a proof pair
+        assert!(!is_proof_pair(
+            &m,
+            &Formula::try_from("[b=0>0=b]")?.arithmetize()
+        ));
+        // a malformed number is never a proof pair
+        assert!(!is_proof_pair(&BigUint::from(12345u32), &a.arithmetize()));
+        assert!(
+            is_proof_pair_with(&BigUint::from(12345u32), &a.arithmetize(), &ByteNumbering).is_err()
+        );
+        assert!(is_proof_pair_with(&m, &m, &ByteNumbering).is_err());
+
+        let m = d.arithmetize_with(&CodonNumbering)?;
+        let n = a.arithmetize_with(&CodonNumbering)?;
+        assert!(is_proof_pair_with(&m, &n, &CodonNumbering)?);
+        assert!(is_proof_pair_with(&BigUint::from(6666u32), &n, &CodonNumbering).is_err());
+
+        // a sequence of Formulas that is not a Deduction
+        let mut s = Formula::try_from("0=S0")?
+            .arithmetize_with(&CodonNumbering)?
+            .to_string();
+        s.push_str("611");
+        let m: BigUint = s.parse().unwrap();
+        let n = Formula::try_from("0=S0")?.arithmetize_with(&CodonNumbering)?;
+        assert!(!is_proof_pair_with(&m, &n, &CodonNumbering)?);
+
+        // the prime power numbers of valid Deductions are far too large to compute but a supposition that is never discharged can be encoded
+        let mut d = Deduction::new("Open");
+        d.supposition(Formula::try_from("0=0")?)?;
+        let m = d.arithmetize_with(&PrimePowerNumbering)?;
+        let n = Formula::try_from("0=0")?.arithmetize_with(&PrimePowerNumbering)?;
+        assert!(!is_proof_pair_with(&m, &n, &PrimePowerNumbering)?);
+        assert!(is_proof_pair_with(&BigUint::from(10u32), &n, &PrimePowerNumbering).is_err());
+        Ok(())
+    }
+}
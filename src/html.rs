@@ -0,0 +1,217 @@
+//! A self-contained HTML page for reading a Deduction in a browser.
+//!
+//! The page needs no network access. Its styles and script are written into the file and Formulas are rendered with MathML, which browsers display natively. Each supposition can be collapsed, hovering over a line highlights the lines it was derived from and the Formulas can be switched between ASCII, pretty, English and MathML renderings.
+//! ```
+//! # use tnt::{Deduction, Term};
+//! let mut d = Deduction::new("One Plus Zero");
+//! d.add_axiom(1).unwrap();
+//! d.specification(0, "a", &Term::one()).unwrap();
+//! let html = d.to_html();
+//! assert!(html.contains("<div class=\"line\" id=\"line-1\" data-premises=\"0\">"));
+//! ```
+
+use std::fs::File;
+use std::io::{Error, Write};
+
+use crate::deduction::ScopeEvent;
+use crate::Deduction;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+.controls { margin-bottom: 1em; }
+.formula { display: none; }
+body.ascii .ascii, body.pretty .pretty, body.english .english, body.mathml .mathml { display: inline; }
+.ascii, .pretty { font-family: monospace; }
+.line { padding: 0.1em 0.3em; }
+.number { display: inline-block; min-width: 3em; color: #666666; }
+.annotation { margin-left: 2em; color: #666666; font-style: italic; }
+.current { background: #d6e6ff; }
+.premise { background: #ffe9a8; }
+details.supposition { border-left: 2px solid #999999; margin: 0.2em 0 0.2em 1em; padding-left: 0.5em; }
+summary { color: #666666; cursor: pointer; }
+";
+
+const SCRIPT: &str = "
+document.querySelectorAll('input[name=view]').forEach(function (input) {
+  input.addEventListener('change', function () {
+    document.body.className = input.value;
+  });
+});
+document.querySelectorAll('.line').forEach(function (line) {
+  var premises = line.dataset.premises.split(' ').filter(function (p) { return p !== ''; });
+  function mark(on) {
+    line.classList.toggle('current', on);
+    premises.forEach(function (p) {
+      document.getElementById('line-' + p).classList.toggle('premise', on);
+    });
+  }
+  line.addEventListener('mouseenter', function () { mark(true); });
+  line.addEventListener('mouseleave', function () { mark(false); });
+});
+";
+
+const VIEWS: [(&str, &str); 4] = [
+    ("ascii", "ASCII"),
+    ("pretty", "Pretty"),
+    ("english", "English"),
+    ("mathml", "MathML"),
+];
+
+// Escape the characters that HTML treats specially.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Deduction {
+    /// A single HTML page displaying the Deduction, as described in the module documentation.
+    pub fn to_html(&self) -> String {
+        let title = escape(&self.title);
+        let mut out = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        out.push_str("<meta charset=\"utf-8\">\n");
+        out.push_str(&format!("<title>{title}</title>\n"));
+        out.push_str(&format!("<style>{STYLE}</style>\n"));
+        out.push_str("</head>\n<body class=\"pretty\">\n");
+        out.push_str(&format!("<h1>{title}</h1>\n"));
+
+        out.push_str("<div class=\"controls\">\n");
+        for (value, name) in VIEWS {
+            let checked = if value == "pretty" { " checked" } else { "" };
+            out.push_str(&format!(
+                "<label><input type=\"radio\" name=\"view\" value=\"{value}\"{checked}> {name}</label>\n"
+            ));
+        }
+        out.push_str("</div>\n<div class=\"deduction\">\n");
+
+        for event in self.scope_events() {
+            let t = match event {
+                ScopeEvent::Open(t) => {
+                    out.push_str(&format!(
+                        "<details class=\"supposition\" open>\n<summary>supposition {}</summary>\n",
+                        t.position
+                    ));
+                    continue;
+                }
+                ScopeEvent::Close(_) => {
+                    out.push_str("</details>\n");
+                    continue;
+                }
+                ScopeEvent::Line(t) => t,
+            };
+            let premises: Vec<String> = t
+                .justification
+                .premises()
+                .iter()
+                .map(|p| p.to_string())
+                .collect();
+            let f = t.formula();
+            out.push_str(&format!(
+                "<div class=\"line\" id=\"line-{}\" data-premises=\"{}\">",
+                t.position,
+                premises.join(" ")
+            ));
+            out.push_str(&format!("<span class=\"number\">{})</span>", t.position));
+            out.push_str(&format!(
+                "<span class=\"formula ascii\">{}</span>",
+                escape(&f.to_string())
+            ));
+            out.push_str(&format!(
+                "<span class=\"formula pretty\">{}</span>",
+                escape(&f.pretty_string())
+            ));
+            out.push_str(&format!(
+                "<span class=\"formula english\">{}</span>",
+                escape(&f.to_english())
+            ));
+            out.push_str(&format!(
+                "<span class=\"formula mathml\"><math>{}</math></span>",
+                f.to_mathml()
+            ));
+            out.push_str(&format!(
+                "<span class=\"annotation\">{}</span></div>\n",
+                escape(&t.annotation())
+            ));
+        }
+
+        out.push_str("</div>\n");
+        out.push_str(&format!("<script>{SCRIPT}</script>\n"));
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Create an HTML file with the given file name that holds the page given by to_html.
+    pub fn html_file(&self, filename: &str) -> Result<(), Error> {
+        let filename = format!("{}.html", filename);
+        let mut file = File::create(filename)?;
+        file.write_all(self.to_html().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{Formula, LogicError, Term};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_mathml() -> Result<(), LogicError> {
+        let t = Term::try_from("S(a''*0)")?;
+        assert_eq!(
+            t.to_mathml(),
+            "<mrow><mi>S</mi><mrow><mo>(</mo><msup><mi>a</mi><mo>′′</mo></msup><mo>·</mo><mn>0</mn><mo>)</mo></mrow></mrow>"
+        );
+        let f = Formula::try_from("Ab:[b=0>~Sb=0]")?;
+        assert_eq!(
+            f.to_mathml(),
+            "<mrow><mo>∀</mo><mi>b</mi><mo>:</mo><mrow><mo>⟨</mo><mrow><mi>b</mi><mo>=</mo><mn>0</mn></mrow><mo>⊃</mo><mrow><mo>¬</mo><mrow><mrow><mi>S</mi><mi>b</mi></mrow><mo>=</mo><mn>0</mn></mrow></mrow><mo>⟩</mo></mrow></mrow>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_html() -> Result<(), LogicError> {
+        let mut d = Deduction::new("<Fantasy> & More");
+        d.add_axiom(1)?;
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.symmetry(1)?;
+        d.implication()?;
+        let html = d.to_html();
+        assert!(html.contains("<title>&lt;Fantasy&gt; &amp; More</title>"));
+        assert!(html.contains("<span class=\"formula ascii\">[a=0&gt;0=a]</span>"));
+        assert!(html.contains("<div class=\"line\" id=\"line-3\" data-premises=\"1 2\">"));
+        assert!(html.contains(
+            "<details class=\"supposition\" open>\n<summary>supposition 1</summary>\n<div class=\"line\" id=\"line-1\""
+        ));
+        assert!(html.contains("</span></div>\n</details>\n<div class=\"line\" id=\"line-3\""));
+
+        // the opening of every element in the body of the page
+        let body =
+            &html[html.find("<div class=\"deduction\">").unwrap()..html.find("<script>").unwrap()];
+        let tags: Vec<&str> = body
+            .lines()
+            .map(|l| &l[..l.find('>').unwrap() + 1])
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                "<div class=\"deduction\">",
+                "<div class=\"line\" id=\"line-0\" data-premises=\"\">",
+                "<details class=\"supposition\" open>",
+                "<summary>",
+                "<div class=\"line\" id=\"line-1\" data-premises=\"\">",
+                "<div class=\"line\" id=\"line-2\" data-premises=\"1\">",
+                "</details>",
+                "<div class=\"line\" id=\"line-3\" data-premises=\"1 2\">",
+                "</div>",
+            ]
+        );
+
+        // nothing is loaded from elsewhere
+        assert!(!html.contains("http"));
+        assert!(!html.contains(" src="));
+        Ok(())
+    }
+}
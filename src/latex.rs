@@ -0,0 +1,245 @@
+//! LaTeX documents displaying a Deduction.
+//!
+//! The listing produced by `write_latex` gives each line with its annotation, indented by the depth of its supposition. The Fitch-style proof produced by `write_fitch` uses the `fitch` package, which draws a scope bar beside each supposition. Lines and axioms are numbered from 0 as in every other display of a Deduction.
+//! ```
+//! # use tnt::{Deduction, Formula};
+//! # use std::convert::TryFrom;
+//! let mut d = Deduction::new("Fantasy");
+//! d.supposition(Formula::try_from("a=0").unwrap()).unwrap();
+//! d.symmetry(0).unwrap();
+//! d.implication().unwrap();
+//! let mut out = Vec::new();
+//! d.write_fitch(&mut out).unwrap();
+//! let tex = String::from_utf8(out).unwrap();
+//! assert!(tex.contains("\\open\n  \\hypo{0}{a = 0}\n"));
+//! assert!(tex.contains("\\close\n  \\have{2}{\\langle a = 0 \\supset 0 = a \\rangle} \\by{implication}{0-1}\n"));
+//! ```
+
+use std::fs::File;
+use std::io::{Error, Write};
+
+use crate::deduction::{Justification, ScopeEvent};
+use crate::Deduction;
+
+// Escape the characters that LaTeX treats specially in text.
+fn escape(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub(crate) fn begin_document(
+    out: &mut impl Write,
+    package: &str,
+    title: &str,
+) -> Result<(), Error> {
+    writeln!(out, "\\documentclass[11pt]{{article}}")?;
+    writeln!(out, "\\usepackage{{amsmath}}")?;
+    writeln!(out, "\\usepackage{{{package}}}")?;
+    writeln!(out, "\\begin{{document}}")?;
+    writeln!(out, "\\section*{{{}}}", escape(title))
+}
+
+// The rule and the lines it cites in the notation of the fitch package.
+fn fitch_justification(justification: &Justification) -> (String, String) {
+    let rule = match justification {
+        Justification::Axiom(n) => format!("axiom {n}"),
+        j => j.rule().to_string(),
+    };
+    let lines = match justification {
+        Justification::Implication {
+            premise,
+            conclusion,
+        } => format!("{premise}-{conclusion}"),
+        j => j
+            .premises()
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    };
+    (rule, lines)
+}
+
+impl Deduction {
+    /// Write a LaTeX document listing the lines of the Deduction with their annotations. Lines inside a supposition are indented.
+    pub fn write_latex(&self, out: &mut impl Write) -> Result<(), Error> {
+        begin_document(out, "longtable", &self.title)?;
+        writeln!(out, "\\begin{{longtable}}{{rll}}")?;
+        let mut prev_depth = 0;
+        for t in self.theorems() {
+            if t.depth > prev_depth {
+                let indent = "\\qquad".repeat(prev_depth);
+                writeln!(out, "& {indent}\\textit{{begin supposition}} & \\\\")?;
+            } else if t.depth < prev_depth {
+                let indent = "\\qquad".repeat(t.depth);
+                writeln!(out, "& {indent}\\textit{{end supposition}} & \\\\")?;
+            }
+            writeln!(
+                out,
+                "{}) & {}${}$ & {} \\\\",
+                t.position,
+                "\\qquad".repeat(t.depth),
+                t.formula().to_latex(),
+                escape(&t.annotation())
+            )?;
+            prev_depth = t.depth;
+        }
+        writeln!(out, "\\end{{longtable}}")?;
+        writeln!(out, "\\end{{document}}")
+    }
+
+    /// Create a LaTeX file with the given file name that lists the Deduction as write_latex does.
+    pub fn latex_file(&self, filename: &str) -> Result<(), Error> {
+        let filename = format!("{}.tex", filename);
+        let mut file = File::create(filename)?;
+        self.write_latex(&mut file)
+    }
+
+    /// Write a LaTeX document displaying the Deduction as a Fitch-style proof with the `fitch` package. Each supposition is drawn inside its own scope bar and every other line cites its rule and the lines it was derived from.
+    pub fn write_fitch(&self, out: &mut impl Write) -> Result<(), Error> {
+        begin_document(out, "fitch", &self.title)?;
+        writeln!(out, "\\[")?;
+        writeln!(out, "\\begin{{nd}}")?;
+        for event in self.scope_events() {
+            match event {
+                ScopeEvent::Open(_) => writeln!(out, "  \\open")?,
+                ScopeEvent::Close(_) => writeln!(out, "  \\close")?,
+                ScopeEvent::Line(t) => {
+                    let formula = t.formula().to_latex();
+                    if t.justification == Justification::Supposition {
+                        writeln!(out, "  \\hypo{{{}}}{{{formula}}}", t.position)?;
+                    } else {
+                        let (rule, lines) = fitch_justification(&t.justification);
+                        writeln!(
+                            out,
+                            "  \\have{{{}}}{{{formula}}} \\by{{{rule}}}{{{lines}}}",
+                            t.position
+                        )?;
+                    }
+                }
+            }
+        }
+        writeln!(out, "\\end{{nd}}")?;
+        writeln!(out, "\\]")?;
+        writeln!(out, "\\end{{document}}")
+    }
+
+    /// Create a LaTeX file with the given file name that displays the Deduction as write_fitch does.
+    pub fn fitch_file(&self, filename: &str) -> Result<(), Error> {
+        let filename = format!("{}.tex", filename);
+        let mut file = File::create(filename)?;
+        self.write_fitch(&mut file)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{Formula, LogicError, Term};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_to_latex() -> Result<(), LogicError> {
+        let f = Formula::try_from("Aa:[(a*Sb)=0>~Eb:(S0+b)=a]")?;
+        assert_eq!(
+            f.to_latex(),
+            "\\forall a: \\langle (a \\cdot Sb) = 0 \\supset \\neg \\exists b: (S0 + b) = a \\rangle"
+        );
+        let t = Term::try_from("S(a*(b+0))")?;
+        assert_eq!(t.to_latex(), "S(a \\cdot (b + 0))");
+        Ok(())
+    }
+
+    // A supposition between two lines at the top level.
+    fn fantasy() -> Result<Deduction, LogicError> {
+        let mut d = Deduction::new("Fantasy & 100% of #_");
+        d.add_axiom(1)?;
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.specification(0, "a", &Term::var("a"))?;
+        d.transitivity(2, 1)?;
+        d.implication()?;
+        Ok(d)
+    }
+
+    #[test]
+    fn test_latex() -> Result<(), LogicError> {
+        let mut out = Vec::new();
+        fantasy()?.write_latex(&mut out).unwrap();
+        let expected = "\
+\\documentclass[11pt]{article}
+\\usepackage{amsmath}
+\\usepackage{longtable}
+\\begin{document}
+\\section*{Fantasy \\& 100\\% of \\#\\_}
+\\begin{longtable}{rll}
+0) & $\\forall a: (a + 0) = a$ & axiom \\\\
+& \\textit{begin supposition} & \\\\
+1) & \\qquad$a = 0$ & supposition \\\\
+2) & \\qquad$(a + 0) = a$ & specification of a to a in theorem 0 \\\\
+3) & \\qquad$(a + 0) = 0$ & transitivity of theorem 2 and theorem 1 \\\\
+& \\textit{end supposition} & \\\\
+4) & $\\langle a = 0 \\supset (a + 0) = 0 \\rangle$ & implication of theorem 1 and theorem 3 \\\\
+\\end{longtable}
+\\end{document}
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fitch() -> Result<(), LogicError> {
+        let mut out = Vec::new();
+        fantasy()?.write_fitch(&mut out).unwrap();
+        let expected = "\
+\\documentclass[11pt]{article}
+\\usepackage{amsmath}
+\\usepackage{fitch}
+\\begin{document}
+\\section*{Fantasy \\& 100\\% of \\#\\_}
+\\[
+\\begin{nd}
+  \\have{0}{\\forall a: (a + 0) = a} \\by{axiom 1}{}
+  \\open
+  \\hypo{1}{a = 0}
+  \\have{2}{(a + 0) = a} \\by{specification}{0}
+  \\have{3}{(a + 0) = 0} \\by{transitivity}{2,1}
+  \\close
+  \\have{4}{\\langle a = 0 \\supset (a + 0) = 0 \\rangle} \\by{implication}{1-3}
+\\end{nd}
+\\]
+\\end{document}
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fitch_open() -> Result<(), LogicError> {
+        // suppositions that are never discharged are still closed
+        let mut d = Deduction::new("Open");
+        d.add_axiom(1)?;
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.supposition(Formula::try_from("b=0")?)?;
+        d.specification(0, "a", &Term::zero())?;
+        let mut out = Vec::new();
+        d.write_fitch(&mut out).unwrap();
+        let tex = String::from_utf8(out).unwrap();
+        assert_eq!(tex.matches("\\close").count(), 2);
+        assert!(tex.contains("\\have{3}{(0 + 0) = 0} \\by{specification}{0}"));
+        assert!(tex.contains("\\have{0}{\\forall a: (a + 0) = a} \\by{axiom 1}{}"));
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Checked proofs of standard facts of arithmetic. Each function builds a new Deduction from the Peano axioms and returns it along with the position of its conclusion.
+//!
+//! The conclusions quantify over the variables c, d and e so that the axioms, which use a and b, can be specified to Terms containing them.
+//! ```
+//! # use tnt::lemmas;
+//! let (d, n) = lemmas::addition_commutes().unwrap();
+//! assert_eq!(d.theorem(n).formula().to_string(), "Ac:Ad:(c+d)=(d+c)");
+//! ```
+
+use crate::{Deduction, LogicError};
+
+fn prove(
+    title: &str,
+    lemma: impl FnOnce(&mut Deduction) -> Result<usize, LogicError>,
+) -> Result<(Deduction, usize), LogicError> {
+    let mut d = Deduction::new(title);
+    let n = lemma(&mut d)?;
+    Ok((d, n))
+}
+
+/// Ac:(0+c)=c
+pub fn zero_plus() -> Result<(Deduction, usize), LogicError> {
+    prove("Zero Plus", |d| d.zero_sum_lemma("c"))
+}
+
+/// Ac:Ad:(Sc+d)=S(c+d)
+pub fn successor_plus() -> Result<(Deduction, usize), LogicError> {
+    prove("Successor Plus", |d| d.succ_sum_lemma("c", "d"))
+}
+
+/// Ac:Ad:(c+d)=(d+c)
+pub fn addition_commutes() -> Result<(Deduction, usize), LogicError> {
+    prove("Addition Commutes", |d| d.sum_commutes_lemma("c", "d"))
+}
+
+/// Ac:Ad:Ae:((c+d)+e)=(c+(d+e))
+pub fn addition_associates() -> Result<(Deduction, usize), LogicError> {
+    prove("Addition Associates", |d| {
+        d.sum_associates_lemma("c", "d", "e")
+    })
+}
+
+/// Ac:Ad:Ae:[(c+e)=(d+e)>c=d]
+pub fn addition_cancels() -> Result<(Deduction, usize), LogicError> {
+    prove("Addition Cancels", |d| d.sum_cancels_lemma("c", "d", "e"))
+}
+
+/// Ac:(0*c)=0
+pub fn zero_times() -> Result<(Deduction, usize), LogicError> {
+    prove("Zero Times", |d| d.zero_product_lemma("c"))
+}
+
+/// Ac:(S0*c)=c
+pub fn one_times() -> Result<(Deduction, usize), LogicError> {
+    prove("One Times", |d| d.one_times_lemma("c"))
+}
+
+/// Ac:(c*S0)=c
+pub fn times_one() -> Result<(Deduction, usize), LogicError> {
+    prove("Times One", |d| d.times_one_lemma("c"))
+}
+
+/// Ac:Ad:(Sc*d)=((c*d)+d)
+pub fn successor_times() -> Result<(Deduction, usize), LogicError> {
+    prove("Successor Times", |d| d.succ_product_lemma("c", "d"))
+}
+
+/// Ac:Ad:(c*d)=(d*c)
+pub fn multiplication_commutes() -> Result<(Deduction, usize), LogicError> {
+    prove("Multiplication Commutes", |d| {
+        d.product_commutes_lemma("c", "d")
+    })
+}
+
+/// Ac:Ad:Ae:((c*d)*e)=(c*(d*e))
+pub fn multiplication_associates() -> Result<(Deduction, usize), LogicError> {
+    prove("Multiplication Associates", |d| {
+        d.product_associates_lemma("c", "d", "e")
+    })
+}
+
+/// Ac:Ad:Ae:(c*(d+e))=((c*d)+(c*e))
+pub fn distributivity() -> Result<(Deduction, usize), LogicError> {
+    prove("Distributivity", |d| {
+        d.product_distributes_lemma("c", "d", "e")
+    })
+}
+
+/// Ac:Ad:Ae:[c=d>(c+e)=(d+e)]
+pub fn addition_congruence() -> Result<(Deduction, usize), LogicError> {
+    prove("Addition Congruence", |d| {
+        d.sum_congruence_left_lemma("c", "d", "e")
+    })
+}
+
+/// Ac:Ad:Ae:[c=d>(e*c)=(e*d)]
+pub fn multiplication_congruence() -> Result<(Deduction, usize), LogicError> {
+    prove("Multiplication Congruence", |d| {
+        d.product_congruence_right_lemma("c", "d", "e")
+    })
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    type Lemma = fn() -> Result<(Deduction, usize), LogicError>;
+
+    #[test]
+    fn test_lemmas() -> Result<(), LogicError> {
+        let cases: Vec<(Lemma, &str)> = vec![
+            (zero_plus, "Ac:(0+c)=c"),
+            (successor_plus, "Ac:Ad:(Sc+d)=S(c+d)"),
+            (addition_commutes, "Ac:Ad:(c+d)=(d+c)"),
+            (addition_associates, "Ac:Ad:Ae:((c+d)+e)=(c+(d+e))"),
+            (addition_cancels, "Ac:Ad:Ae:[(c+e)=(d+e)>c=d]"),
+            (zero_times, "Ac:(0*c)=0"),
+            (one_times, "Ac:(S0*c)=c"),
+            (times_one, "Ac:(c*S0)=c"),
+            (successor_times, "Ac:Ad:(Sc*d)=((c*d)+d)"),
+            (multiplication_commutes, "Ac:Ad:(c*d)=(d*c)"),
+            (multiplication_associates, "Ac:Ad:Ae:((c*d)*e)=(c*(d*e))"),
+            (distributivity, "Ac:Ad:Ae:(c*(d+e))=((c*d)+(c*e))"),
+            (addition_congruence, "Ac:Ad:Ae:[c=d>(c+e)=(d+e)]"),
+            (multiplication_congruence, "Ac:Ad:Ae:[c=d>(e*c)=(e*d)]"),
+        ];
+        for (lemma, statement) in cases {
+            let (d, n) = lemma()?;
+            assert_eq!(d.theorem(n).formula().to_string(), statement);
+            assert_eq!(n, d.last_theorem().position);
+            assert_eq!(d.depth(), 0);
+        }
+        Ok(())
+    }
+}
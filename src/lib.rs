@@ -1,14 +1,35 @@
 pub mod deduction;
+mod derivations;
 pub use deduction::Deduction;
 pub mod formula;
 pub use formula::Formula;
 pub mod term;
 pub use term::Term;
+pub mod godel;
+pub mod html;
+pub mod latex;
+pub mod lemmas;
 pub mod logic_errors;
 pub use logic_errors::LogicError;
 pub mod production;
 pub use production::*;
+pub mod calc;
+pub use calc::Calc;
+pub mod check;
+pub use check::check;
+pub mod decision;
+pub use decision::decide;
+pub mod dot;
+pub mod presburger;
+pub use presburger::decide_presburger;
+pub mod annotated;
+pub mod numbering;
 pub mod parsing;
+pub mod script;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod tactics;
+pub mod tree;
 
 #[macro_use]
 extern crate pest_derive;
@@ -0,0 +1,332 @@
+//! Schemes for turning the symbols of TNT into numbers and back.
+//!
+//! A scheme encodes a string of TNT symbols in its austere form, where every variable is `a` followed by some number of primes. The formulas of a Deduction are separated by the ASCII null symbol, which each scheme encodes as its own separator.
+//! ```
+//! # use tnt::{Formula, numbering::CodonNumbering};
+//! # use std::convert::TryFrom;
+//! let f = Formula::try_from("Aa:~Sa=0").unwrap();
+//! assert_eq!(f.arithmetize_with(&CodonNumbering).unwrap().to_string(), "626262636223123262111666");
+//! ```
+
+use num::BigUint;
+use std::convert::TryFrom;
+
+use crate::LogicError;
+
+/// A Gödel numbering of the symbols of TNT.
+pub trait GodelNumbering {
+    /// Encode a string of TNT symbols. The ASCII null symbol separates the formulas of a Deduction.
+    fn encode(&self, symbols: &str) -> Result<BigUint, LogicError>;
+
+    /// Recover the string of TNT symbols encoded by a number.
+    fn decode(&self, number: &BigUint) -> Result<String, LogicError>;
+}
+
+/// Read the ASCII bytes of the symbols as a big-endian number. This is the scheme used by `arithmetize`.
+#[derive(Copy, Clone, Debug)]
+pub struct ByteNumbering;
+
+impl GodelNumbering for ByteNumbering {
+    fn encode(&self, symbols: &str) -> Result<BigUint, LogicError> {
+        Ok(BigUint::from_bytes_be(symbols.as_bytes()))
+    }
+
+    fn decode(&self, number: &BigUint) -> Result<String, LogicError> {
+        String::from_utf8(number.to_bytes_be()).map_err(|e| LogicError(e.to_string()))
+    }
+}
+
+/// The scheme from Gödel, Escher, Bach where each symbol is a three digit codon and the codons are read as a decimal number. Angle brackets are used for the square brackets of this crate and 611, the codon of punctuation, separates the formulas of a Deduction.
+#[derive(Copy, Clone, Debug)]
+pub struct CodonNumbering;
+
+const CODONS: [(char, u32); 19] = [
+    ('0', 666),
+    ('S', 123),
+    ('=', 111),
+    ('+', 112),
+    ('*', 236),
+    ('(', 362),
+    (')', 323),
+    ('[', 212),
+    (']', 213),
+    ('a', 262),
+    ('\'', 163),
+    ('&', 161),
+    ('|', 616),
+    ('>', 633),
+    ('~', 223),
+    ('E', 333),
+    ('A', 626),
+    (':', 636),
+    ('\0', 611),
+];
+
+impl GodelNumbering for CodonNumbering {
+    fn encode(&self, symbols: &str) -> Result<BigUint, LogicError> {
+        let mut digits = String::new();
+        for c in symbols.chars() {
+            match CODONS.iter().find(|(s, _)| *s == c) {
+                Some((_, codon)) => digits.push_str(&codon.to_string()),
+                None => {
+                    return Err(LogicError(format!(
+                        "Numbering Error: `{c}` has no codon, variables must be in austere form"
+                    )))
+                }
+            }
+        }
+        if digits.is_empty() {
+            return Ok(BigUint::from(0u32));
+        }
+        Ok(digits.parse().unwrap())
+    }
+
+    // usize::is_multiple_of needs a newer compiler than the crate otherwise requires
+    #[allow(clippy::manual_is_multiple_of)]
+    fn decode(&self, number: &BigUint) -> Result<String, LogicError> {
+        let digits = number.to_string();
+        if digits == "0" {
+            return Ok(String::new());
+        }
+        if digits.len() % 3 != 0 {
+            return Err(LogicError(format!(
+                "Numbering Error: {number} is not a sequence of codons"
+            )));
+        }
+        let mut out = String::new();
+        for chunk in digits.as_bytes().chunks(3) {
+            let codon: u32 = std::str::from_utf8(chunk).unwrap().parse().unwrap();
+            match CODONS.iter().find(|(_, c)| *c == codon) {
+                Some((s, _)) => out.push(*s),
+                None => {
+                    return Err(LogicError(format!(
+                        "Numbering Error: {codon} is not a codon"
+                    )))
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Gödel's original scheme where the nth symbol, with code c, contributes the factor p^c for the nth prime p. The codes run from 1 to 18 in the order `0 S = + * ( ) [ ] a ' & | > ~ E A :`.
+///
+/// The formulas of a Deduction are encoded as a sequence in the same way, with the number of each Formula as the exponent of its prime. These numbers grow far too quickly to be computed for any but the smallest Deductions so an error is returned if the number of a Formula exceeds `MAX_EXPONENT`.
+/// ```
+/// # use tnt::numbering::{GodelNumbering, PrimePowerNumbering};
+/// let n = PrimePowerNumbering.encode("0=0").unwrap();
+/// assert_eq!(n.to_string(), "270"); // 2^1 * 3^3 * 5^1
+/// assert_eq!(PrimePowerNumbering.decode(&n).unwrap(), "0=0");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct PrimePowerNumbering;
+
+const PRIME_POWER_SYMBOLS: &str = "0S=+*()[]a'&|>~EA:";
+
+impl PrimePowerNumbering {
+    /// The largest Formula number that can be used as an exponent when encoding a Deduction.
+    pub const MAX_EXPONENT: u32 = 1 << 20;
+
+    // Encode a string with no separators as a product of prime powers.
+    fn encode_flat(symbols: &str) -> Result<BigUint, LogicError> {
+        let mut out = BigUint::from(1u32);
+        for (c, p) in symbols.chars().zip(Primes::new()) {
+            let code = match PRIME_POWER_SYMBOLS.chars().position(|s| s == c) {
+                Some(n) => n as u32 + 1,
+                None => {
+                    return Err(LogicError(format!(
+                        "Numbering Error: `{c}` has no code, variables must be in austere form"
+                    )))
+                }
+            };
+            out *= BigUint::from(p).pow(code);
+        }
+        Ok(out)
+    }
+
+    // Decode a product of prime powers whose exponents are all codes.
+    fn decode_flat(exponents: &[BigUint]) -> Result<String, LogicError> {
+        let mut out = String::new();
+        for e in exponents {
+            let code = e.to_u32_digits().first().copied().unwrap_or(0) as usize;
+            match PRIME_POWER_SYMBOLS.chars().nth(code.wrapping_sub(1)) {
+                Some(c) if e.bits() <= 32 => out.push(c),
+                _ => {
+                    return Err(LogicError(format!(
+                        "Numbering Error: {e} is not the code of a symbol"
+                    )))
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    // Divide out every factor of p and return how many there were. Dividing by p, p^2, p^4 and so on keeps the number of divisions small even for very large exponents.
+    fn divide_out(n: &mut BigUint, p: &BigUint) -> BigUint {
+        let zero = BigUint::from(0u32);
+        let mut powers = vec![p.clone()];
+        let mut e = BigUint::from(0u32);
+        while (&*n % powers.last().unwrap()) == zero {
+            *n /= powers.last().unwrap();
+            e += BigUint::from(1u32) << (powers.len() - 1);
+            let square = powers.last().unwrap() * powers.last().unwrap();
+            powers.push(square);
+        }
+        for (i, q) in powers.iter().enumerate().rev() {
+            if (&*n % q) == zero {
+                *n /= q;
+                e += BigUint::from(1u32) << i;
+            }
+        }
+        e
+    }
+
+    // The exponents of the primes in order, stopping at the largest prime that divides the number. Every smaller prime must divide it as well.
+    fn exponents(number: &BigUint) -> Result<Vec<BigUint>, LogicError> {
+        let zero = BigUint::from(0u32);
+        if number == &zero {
+            return Err(LogicError(
+                "Numbering Error: 0 is not a product of prime powers".to_string(),
+            ));
+        }
+        let mut n = number.clone();
+        let mut out = Vec::new();
+        let one = BigUint::from(1u32);
+        for p in Primes::new() {
+            if n == one {
+                break;
+            }
+            let e = Self::divide_out(&mut n, &BigUint::from(p));
+            if e == zero {
+                return Err(LogicError(format!(
+                    "Numbering Error: {number} is not divisible by each prime up to the largest that divides it"
+                )));
+            }
+            out.push(e);
+        }
+        Ok(out)
+    }
+}
+
+impl GodelNumbering for PrimePowerNumbering {
+    fn encode(&self, symbols: &str) -> Result<BigUint, LogicError> {
+        if !symbols.contains('\0') {
+            return Self::encode_flat(symbols);
+        }
+        let formulas = symbols.strip_suffix('\0').unwrap_or(symbols);
+        let mut out = BigUint::from(1u32);
+        for (f, p) in formulas.split('\0').zip(Primes::new()) {
+            let g = Self::encode_flat(f)?;
+            match u32::try_from(&g) {
+                Ok(e) if e <= Self::MAX_EXPONENT => out *= BigUint::from(p).pow(e),
+                _ => {
+                    return Err(LogicError(format!(
+                        "Numbering Error: the number of `{f}` is {g} which is too large to be an exponent"
+                    )))
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn decode(&self, number: &BigUint) -> Result<String, LogicError> {
+        let exponents = Self::exponents(number)?;
+        // Every Formula has at least three symbols so its number is larger than any code, which marks a sequence of Formulas
+        let codes = PRIME_POWER_SYMBOLS.len();
+        if exponents.iter().all(|e| e <= &BigUint::from(codes)) {
+            return Self::decode_flat(&exponents);
+        }
+        let mut out = String::new();
+        for e in exponents {
+            out.push_str(&Self::decode_flat(&Self::exponents(&e)?)?);
+            out.push('\0');
+        }
+        Ok(out)
+    }
+}
+
+// The prime numbers in order.
+struct Primes {
+    found: Vec<u64>,
+}
+
+impl Primes {
+    fn new() -> Self {
+        Primes { found: Vec::new() }
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    // u64::is_multiple_of needs a newer compiler than the crate otherwise requires
+    #[allow(clippy::manual_is_multiple_of)]
+    fn next(&mut self) -> Option<u64> {
+        let mut n = self.found.last().map_or(2, |p| p + 1);
+        while self
+            .found
+            .iter()
+            .take_while(|p| *p * *p <= n)
+            .any(|p| n % p == 0)
+        {
+            n += 1;
+        }
+        self.found.push(n);
+        Some(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_codons() -> Result<(), LogicError> {
+        let s = "Aa:Aa':[(a+Sa')=0>~a=a']\0Ea:a=a";
+        let n = CodonNumbering.encode(s)?;
+        assert!(n
+            .to_string()
+            .starts_with("626262636626262163636212362262112123262163323111666633"));
+        assert_eq!(CodonNumbering.decode(&n)?, s);
+        assert_eq!(ByteNumbering.decode(&ByteNumbering.encode(s)?)?, s);
+        assert!(CodonNumbering.encode("b=b").is_err());
+        assert!(CodonNumbering.decode(&BigUint::from(6666u32)).is_err());
+        assert!(CodonNumbering.decode(&BigUint::from(999u32)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_prime_powers() -> Result<(), LogicError> {
+        let primes: Vec<u64> = Primes::new().take(8).collect();
+        assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19]);
+
+        let s = "Aa:Aa':[(a+Sa')=0>~a=a']";
+        let n = PrimePowerNumbering.encode(s)?;
+        assert_eq!(PrimePowerNumbering.decode(&n)?, s);
+
+        // a sequence of formulas, 2^(2^1*3^3*5^1) * 3^(2^2*3^1*5^3*7^1)
+        let s = "0=0\0S0=0\0";
+        let n = PrimePowerNumbering.encode(s)?;
+        assert_eq!(
+            n,
+            BigUint::from(2u32).pow(270) * BigUint::from(3u32).pow(10500)
+        );
+        assert_eq!(PrimePowerNumbering.decode(&n)?, s);
+
+        // the numbers of most formulas are far too large to be exponents
+        assert!(PrimePowerNumbering.encode("Aa:~Sa=0\0").is_err());
+        let mut d = crate::Deduction::new("Too Large");
+        d.add_axiom(0)?;
+        assert!(d.arithmetize_with(&PrimePowerNumbering).is_err());
+        assert!(PrimePowerNumbering.encode("b=b").is_err());
+        // 5 divides the number but 3 does not
+        assert!(PrimePowerNumbering.decode(&BigUint::from(10u32)).is_err());
+        assert!(PrimePowerNumbering.decode(&BigUint::from(0u32)).is_err());
+        // 2^19 is not the code of a symbol and not the number of a formula
+        assert!(PrimePowerNumbering
+            .decode(&BigUint::from(1u32 << 19))
+            .is_err());
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use pest::{iterators::Pair, Parser};
 use crate::{Formula, Term};
 
 #[derive(Parser)]
-#[grammar = "parsing\\tnt.pest"]
+#[grammar = "parsing/tnt.pest"]
 pub struct TntParser;
 
 // fn print_parse_tree(text: &str, rule: Rule) -> Result<(), pest::error::Error<Rule>> {
@@ -12,11 +12,13 @@ pub struct TntParser;
 //     Ok(())
 // }
 
+#[allow(clippy::result_large_err)]
 pub fn string_to_formula(text: &str) -> Result<Formula, pest::error::Error<Rule>> {
     let mut tree = TntParser::parse(Rule::formula, text)?;
     Ok(build_formula_tree(tree.next().unwrap()))
 }
 
+#[allow(clippy::result_large_err)]
 pub fn string_to_term(text: &str) -> Result<Term, pest::error::Error<Rule>> {
     let mut tree = TntParser::parse(Rule::term, text)?;
     Ok(build_term_tree(tree.next().unwrap()))
@@ -0,0 +1,396 @@
+//! Decide the truth of Formulas of Presburger arithmetic, those that do not multiply Variables together, using Cooper's quantifier elimination algorithm.
+
+use indexmap::IndexSet;
+use num::{BigInt, Integer, One, Signed, Zero};
+use std::collections::BTreeMap;
+
+use crate::{Formula, LogicError, Term};
+
+// A linear combination of Variables with integer coefficients plus an integer constant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Linear {
+    constant: BigInt,
+    coeffs: BTreeMap<String, BigInt>,
+}
+
+impl Linear {
+    fn constant(n: BigInt) -> Linear {
+        Linear {
+            constant: n,
+            coeffs: BTreeMap::new(),
+        }
+    }
+
+    fn var(name: &str) -> Linear {
+        let mut coeffs = BTreeMap::new();
+        coeffs.insert(name.to_string(), BigInt::one());
+        Linear {
+            constant: BigInt::zero(),
+            coeffs,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    fn coeff(&self, name: &str) -> BigInt {
+        self.coeffs.get(name).cloned().unwrap_or_else(BigInt::zero)
+    }
+
+    fn set_coeff(&mut self, name: &str, c: BigInt) {
+        if c.is_zero() {
+            self.coeffs.remove(name);
+        } else {
+            self.coeffs.insert(name.to_string(), c);
+        }
+    }
+
+    fn add(&self, other: &Linear) -> Linear {
+        let mut out = self.clone();
+        out.constant += &other.constant;
+        for (v, c) in other.coeffs.iter() {
+            let sum = out.coeff(v) + c;
+            out.set_coeff(v, sum);
+        }
+        out
+    }
+
+    fn scale(&self, k: &BigInt) -> Linear {
+        if k.is_zero() {
+            return Linear::constant(BigInt::zero());
+        }
+        Linear {
+            constant: &self.constant * k,
+            coeffs: self
+                .coeffs
+                .iter()
+                .map(|(v, c)| (v.clone(), c * k))
+                .collect(),
+        }
+    }
+
+    fn neg(&self) -> Linear {
+        self.scale(&-BigInt::one())
+    }
+
+    // Replace the Variable with another linear combination.
+    fn substitute(&self, name: &str, value: &Linear) -> Linear {
+        let c = self.coeff(name);
+        let mut rest = self.clone();
+        rest.set_coeff(name, BigInt::zero());
+        rest.add(&value.scale(&c))
+    }
+}
+
+// A quantifier-free formula in negation normal form. Atoms are 0<t, d|t and ~d|t.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Qf {
+    True,
+    False,
+    Lt(Linear),
+    Dvd(BigInt, Linear),
+    NDvd(BigInt, Linear),
+    And(Box<Qf>, Box<Qf>),
+    Or(Box<Qf>, Box<Qf>),
+}
+
+impl Qf {
+    fn lt(t: Linear) -> Qf {
+        if t.is_constant() {
+            Qf::from_bool(t.constant.is_positive())
+        } else {
+            Qf::Lt(t)
+        }
+    }
+
+    fn dvd(d: BigInt, t: Linear) -> Qf {
+        if d.is_one() {
+            Qf::True
+        } else if t.is_constant() {
+            Qf::from_bool(t.constant.mod_floor(&d).is_zero())
+        } else {
+            Qf::Dvd(d, t)
+        }
+    }
+
+    fn ndvd(d: BigInt, t: Linear) -> Qf {
+        Qf::dvd(d, t).negate()
+    }
+
+    fn from_bool(b: bool) -> Qf {
+        if b {
+            Qf::True
+        } else {
+            Qf::False
+        }
+    }
+
+    fn and(lhs: Qf, rhs: Qf) -> Qf {
+        match (lhs, rhs) {
+            (Qf::False, _) | (_, Qf::False) => Qf::False,
+            (Qf::True, q) | (q, Qf::True) => q,
+            (l, r) => Qf::And(Box::new(l), Box::new(r)),
+        }
+    }
+
+    fn or(lhs: Qf, rhs: Qf) -> Qf {
+        match (lhs, rhs) {
+            (Qf::True, _) | (_, Qf::True) => Qf::True,
+            (Qf::False, q) | (q, Qf::False) => q,
+            (l, r) => Qf::Or(Box::new(l), Box::new(r)),
+        }
+    }
+
+    // Negation is pushed down to the atoms. The negation of 0<t is 0<1-t.
+    fn negate(self) -> Qf {
+        match self {
+            Qf::True => Qf::False,
+            Qf::False => Qf::True,
+            Qf::Lt(t) => Qf::lt(t.neg().add(&Linear::constant(BigInt::one()))),
+            Qf::Dvd(d, t) => Qf::NDvd(d, t),
+            Qf::NDvd(d, t) => Qf::Dvd(d, t),
+            Qf::And(l, r) => Qf::or(l.negate(), r.negate()),
+            Qf::Or(l, r) => Qf::and(l.negate(), r.negate()),
+        }
+    }
+
+    // Rebuild the formula with every atom transformed.
+    fn map_atoms(&self, f: &dyn Fn(&Qf) -> Qf) -> Qf {
+        match self {
+            Qf::And(l, r) => Qf::and(l.map_atoms(f), r.map_atoms(f)),
+            Qf::Or(l, r) => Qf::or(l.map_atoms(f), r.map_atoms(f)),
+            atom => f(atom),
+        }
+    }
+
+    fn atoms<'a>(&'a self, out: &mut Vec<&'a Qf>) {
+        match self {
+            Qf::And(l, r) | Qf::Or(l, r) => {
+                l.atoms(out);
+                r.atoms(out);
+            }
+            atom => out.push(atom),
+        }
+    }
+
+    fn substitute(&self, name: &str, value: &Linear) -> Qf {
+        self.map_atoms(&|atom| match atom {
+            Qf::Lt(t) => Qf::lt(t.substitute(name, value)),
+            Qf::Dvd(d, t) => Qf::dvd(d.clone(), t.substitute(name, value)),
+            Qf::NDvd(d, t) => Qf::ndvd(d.clone(), t.substitute(name, value)),
+            other => other.clone(),
+        })
+    }
+}
+
+fn linear(term: &Term) -> Result<Linear, LogicError> {
+    match term {
+        Term::Zero => Ok(Linear::constant(BigInt::zero())),
+        Term::Variable(v) => Ok(Linear::var(v)),
+        Term::Successor(inner) => Ok(linear(inner)?.add(&Linear::constant(BigInt::one()))),
+        Term::Sum(lhs, rhs) => Ok(linear(lhs)?.add(&linear(rhs)?)),
+        Term::Product(lhs, rhs) => {
+            let l = linear(lhs)?;
+            let r = linear(rhs)?;
+            if l.is_constant() {
+                Ok(r.scale(&l.constant))
+            } else if r.is_constant() {
+                Ok(l.scale(&r.constant))
+            } else {
+                Err(LogicError(format!(
+                    "Presburger Error: the Term `{term}` is a product of two Terms that both contain a Term::Variable"
+                )))
+            }
+        }
+    }
+}
+
+// Cooper's algorithm. Produce a quantifier-free formula equivalent to Ex:q over the integers.
+fn eliminate(x: &str, q: &Qf) -> Qf {
+    let mut atoms = Vec::new();
+    q.atoms(&mut atoms);
+
+    // Scale every atom so that x has the same coefficient everywhere, then replace l*x with x.
+    let l = atoms
+        .iter()
+        .filter_map(|atom| match atom {
+            Qf::Lt(t) | Qf::Dvd(_, t) | Qf::NDvd(_, t) => Some(t.coeff(x).abs()),
+            _ => None,
+        })
+        .filter(|c| !c.is_zero())
+        .fold(BigInt::one(), |acc, c| acc.lcm(&c));
+    let unit = |t: &Linear| -> (BigInt, Linear) {
+        let c = t.coeff(x);
+        if c.is_zero() {
+            return (BigInt::one(), t.clone());
+        }
+        let m = &l / c.abs();
+        let mut out = t.scale(&m);
+        out.set_coeff(x, c.signum());
+        (m, out)
+    };
+    let mut q = q.map_atoms(&|atom| match atom {
+        Qf::Lt(t) => Qf::lt(unit(t).1),
+        Qf::Dvd(d, t) => {
+            let (m, t) = unit(t);
+            Qf::dvd(d * m, t)
+        }
+        Qf::NDvd(d, t) => {
+            let (m, t) = unit(t);
+            Qf::ndvd(d * m, t)
+        }
+        other => other.clone(),
+    });
+    q = Qf::and(q, Qf::dvd(l, Linear::var(x)));
+
+    let mut atoms = Vec::new();
+    q.atoms(&mut atoms);
+    let mut delta = BigInt::one();
+    let mut lower_bounds: Vec<Linear> = Vec::new();
+    for atom in atoms {
+        match atom {
+            Qf::Dvd(d, t) | Qf::NDvd(d, t) if !t.coeff(x).is_zero() => delta = delta.lcm(d),
+            Qf::Lt(t) if t.coeff(x).is_one() => {
+                let mut b = t.neg();
+                b.set_coeff(x, BigInt::zero());
+                if !lower_bounds.contains(&b) {
+                    lower_bounds.push(b);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // The formula for arbitrarily small x in which every lower bound is false and every upper bound is true.
+    let minf = q.map_atoms(&|atom| match atom {
+        Qf::Lt(t) if t.coeff(x).is_one() => Qf::False,
+        Qf::Lt(t) if t.coeff(x) == -BigInt::one() => Qf::True,
+        other => other.clone(),
+    });
+
+    let mut out = Qf::False;
+    let mut j = BigInt::one();
+    while j <= delta {
+        let jl = Linear::constant(j.clone());
+        out = Qf::or(out, minf.substitute(x, &jl));
+        for b in lower_bounds.iter() {
+            out = Qf::or(out, q.substitute(x, &b.add(&jl)));
+        }
+        if out == Qf::True {
+            break;
+        }
+        j += BigInt::one();
+    }
+    out
+}
+
+fn convert(formula: &Formula) -> Result<Qf, LogicError> {
+    match formula {
+        Formula::Equality(lhs, rhs) => {
+            let t = linear(lhs)?.add(&linear(rhs)?.neg());
+            let one = Linear::constant(BigInt::one());
+            Ok(Qf::and(Qf::lt(t.add(&one)), Qf::lt(t.neg().add(&one))))
+        }
+        Formula::Negation(inner) => Ok(convert(inner)?.negate()),
+        Formula::And(lhs, rhs) => Ok(Qf::and(convert(lhs)?, convert(rhs)?)),
+        Formula::Or(lhs, rhs) => Ok(Qf::or(convert(lhs)?, convert(rhs)?)),
+        Formula::Implies(lhs, rhs) => Ok(Qf::or(convert(lhs)?.negate(), convert(rhs)?)),
+        // Variables range over the natural numbers so each quantification also asserts 0<x+1.
+        Formula::Existential(x, inner) => {
+            let natural = Qf::lt(Linear::var(x).add(&Linear::constant(BigInt::one())));
+            Ok(eliminate(x, &Qf::and(convert(inner)?, natural)))
+        }
+        Formula::Universal(x, inner) => {
+            let natural = Qf::lt(Linear::var(x).add(&Linear::constant(BigInt::one())));
+            Ok(eliminate(x, &Qf::and(convert(inner)?.negate(), natural)).negate())
+        }
+    }
+}
+
+/// Determine if a Formula is true in the standard model of arithmetic. The Formula must have no free Variables and no Term::Product may have a Term::Variable on both sides.
+/// ```
+/// # use tnt::Formula;
+/// # use std::convert::TryFrom;
+/// # use tnt::decide_presburger;
+/// let f = &Formula::try_from("Aa:Eb:[(b+b)=a|S(b+b)=a]").unwrap();
+/// assert!(decide_presburger(f).unwrap()); // every number is even or odd
+/// ```
+pub fn decide_presburger(formula: &Formula) -> Result<bool, LogicError> {
+    // A Formula such as a=a may simplify to a constant but is still not a sentence
+    let mut free = IndexSet::new();
+    formula.get_vars_free(&mut free);
+    if !free.is_empty() {
+        return Err(LogicError(format!(
+            "Presburger Error: the Formula `{formula}` contains free Term::Variables"
+        )));
+    }
+    match convert(formula)? {
+        Qf::True => Ok(true),
+        Qf::False => Ok(false),
+        _ => Err(LogicError(format!(
+            "Presburger Error: the Formula `{formula}` contains free Term::Variables"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_parity() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("Aa:Eb:[(b+b)=a|S(b+b)=a]").unwrap();
+        let f2 = &Formula::try_from("Aa:Eb:(b+b)=a").unwrap();
+        let f3 = &Formula::try_from("Aa:~[Eb:(b+b)=a&Eb:S(b+b)=a]").unwrap();
+        assert!(decide_presburger(f1)?);
+        assert!(!decide_presburger(f2)?);
+        assert!(decide_presburger(f3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_axioms() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("Aa:~Sa=0").unwrap();
+        let f2 = &Formula::try_from("Aa:Ab:(a+Sb)=S(a+b)").unwrap();
+        let f3 = &Formula::try_from("Aa:(a*0)=0").unwrap();
+        assert!(decide_presburger(f1)?);
+        assert!(decide_presburger(f2)?);
+        assert!(decide_presburger(f3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_order() -> Result<(), LogicError> {
+        // there is no largest number but there is a smallest
+        let f1 = &Formula::try_from("Ea:Ab:Ec:(b+c)=a").unwrap();
+        let f2 = &Formula::try_from("Ea:Ab:Ec:(a+c)=b").unwrap();
+        // any two numbers are ordered
+        let f3 = &Formula::try_from("Aa:Ab:[Ec:(a+c)=b|Ec:(b+c)=a]").unwrap();
+        assert!(!decide_presburger(f1)?);
+        assert!(decide_presburger(f2)?);
+        assert!(decide_presburger(f3)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeral_products() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("Aa:Eb:[(SSS0*b)=a|[S(b*SSS0)=a|SS(SSS0*b)=a]]").unwrap();
+        let f2 = &Formula::try_from("Ea:(SS0*a)=SSS0").unwrap();
+        assert!(decide_presburger(f1)?);
+        assert!(!decide_presburger(f2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_presburger_err() {
+        let product = &Formula::try_from("Ea:(a*a)=SS0").unwrap();
+        let free = &Formula::try_from("Eb:(b+b)=a").unwrap();
+        assert!(decide_presburger(product).is_err());
+        assert!(decide_presburger(free).is_err());
+        let trivial = &Formula::try_from("a=a").unwrap();
+        assert!(decide_presburger(trivial).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use indexmap::IndexSet;
 
 use std::convert::TryFrom;
 
+use crate::deduction::PEANO_AXIOMS;
 use crate::logic_errors::LogicError;
 use crate::{Formula, Term};
 
@@ -19,7 +20,7 @@ use crate::{Formula, Term};
 /// ```
 pub fn specification(
     formula: &Formula,
-    var_name: &'static str,
+    var_name: &str,
     term: &Term,
 ) -> Result<Formula, LogicError> {
     if formula.contains_var_bound_universal(&var_name) {
@@ -34,7 +35,7 @@ pub fn specification(
             m
         };
         for var in vars_in_term {
-            if bound_in_formula.contains(&var) && term.to_string() != var_name.to_string() {
+            if bound_in_formula.contains(&var) && term.to_string() != var_name {
                 return Err(LogicError(format!("Specification Error: The Term `{}` contains a Term::Variable with the name `{}` which is already bound in the Formula `{}`",term,var_name,formula)));
             }
         }
@@ -248,7 +249,7 @@ pub fn induction(var_name: &str, base: &Formula, general: &Formula) -> Result<Fo
 /// ```
 pub fn successor(formula: &Formula) -> Result<Formula, LogicError> {
     if let Formula::Equality(l, r) = formula {
-        Ok(Formula::eq(&Term::succ(&l), &Term::succ(&r)))
+        Ok(Formula::eq(&Term::succ(l), &Term::succ(r)))
     } else {
         Err(LogicError(format!(
             "Successor Error: {} is not a Formula::Equality",
@@ -259,14 +260,15 @@ pub fn successor(formula: &Formula) -> Result<Formula, LogicError> {
 
 /// Given a Formula::Equality return the predecessor of both sides
 /// ```
-/// use tnt::{Formula,predecessor};
+/// # use tnt::{Formula,predecessor};
+/// # use std::convert::TryFrom;
 /// let f = &Formula::try_from("Sa=Sb").unwrap();
 /// predecessor(f); // a=b
 /// ```
 pub fn predecessor(formula: &Formula) -> Result<Formula, LogicError> {
     if let Formula::Equality(l, r) = formula {
         match (l, r) {
-            (Term::Successor(pl), Term::Successor(pr)) => Ok(Formula::eq(&pl, &pr)),
+            (Term::Successor(pl), Term::Successor(pr)) => Ok(Formula::eq(pl, pr)),
             _ => Err(LogicError(format!(
                 "Predecessor Error: {} does not have Term::Succ on both sides",
                 formula
@@ -290,7 +292,7 @@ pub fn predecessor(formula: &Formula) -> Result<Formula, LogicError> {
 /// ```
 pub fn symmetry(formula: &Formula) -> Result<Formula, LogicError> {
     if let Formula::Equality(l, r) = formula {
-        Ok(Formula::eq(&r, &l))
+        Ok(Formula::eq(r, l))
     } else {
         Err(LogicError(format!(
             "Symmetry Error: {} is not a Formula::Equality",
@@ -305,8 +307,8 @@ pub fn symmetry(formula: &Formula) -> Result<Formula, LogicError> {
 /// # use std::convert::TryFrom;
 /// # use tnt::transitivity;
 /// let f1 = &Formula::try_from("SSa=Sb'").unwrap();
-/// let f2 = &Formula::try_from("Sb'=(1+1)").unwrap();
-/// transitivity(f1,f2); // SSa=(1+1)
+/// let f2 = &Formula::try_from("Sb'=(S0+S0)").unwrap();
+/// transitivity(f1,f2); // SSa=(S0+S0)
 /// ```
 pub fn transitivity(
     left_formula: &Formula,
@@ -315,20 +317,298 @@ pub fn transitivity(
     match (left_formula, right_formula) {
         (Formula::Equality(left_l, left_r), Formula::Equality(right_l, right_r)) => {
             if left_r == right_l {
-                return Ok(Formula::eq(left_l, right_r));
+                Ok(Formula::eq(left_l, right_r))
             } else {
-                return Err(LogicError(format!(
+                Err(LogicError(format!(
                     "Transitivity Error: the terms `{}` and `{}` do not match",
                     left_r, right_l
-                )));
+                )))
             }
         }
+        _ => Err(LogicError(format!(
+            "Transitivity Error: the formulas `{}` and `{}` are not both Formula::Equality",
+            left_formula, right_formula
+        ))),
+    }
+}
+
+/// Given a Formula::Implies return the Formula that the negation of its right side implies the negation of its left side
+/// ```
+/// # use tnt::Formula;
+/// # use std::convert::TryFrom;
+/// # use tnt::contrapositive;
+/// let f = &Formula::try_from("[a=b>Sa=Sb]").unwrap();
+/// contrapositive(f); // [~Sa=Sb>~a=b]
+/// ```
+pub fn contrapositive(formula: &Formula) -> Result<Formula, LogicError> {
+    if let Formula::Implies(l, r) = formula {
+        Ok(Formula::implies(&Formula::not(r), &Formula::not(l)))
+    } else {
+        Err(LogicError(format!(
+            "Contrapositive Error: {} is not a Formula::Implies",
+            formula
+        )))
+    }
+}
+
+/// Given a Formula and a Formula::Implies with that Formula as its left side return the right side of the implication
+/// ```
+/// # use tnt::Formula;
+/// # use std::convert::TryFrom;
+/// # use tnt::detachment;
+/// let f1 = &Formula::try_from("a=b").unwrap();
+/// let f2 = &Formula::try_from("[a=b>Sa=Sb]").unwrap();
+/// detachment(f1,f2); // Sa=Sb
+/// ```
+pub fn detachment(premise: &Formula, implication: &Formula) -> Result<Formula, LogicError> {
+    if let Formula::Implies(l, r) = implication {
+        if **l == *premise {
+            Ok(*r.clone())
+        } else {
+            Err(LogicError(format!(
+                "Detachment Error: the Formula `{}` is not the left side of `{}`",
+                premise, implication
+            )))
+        }
+    } else {
+        Err(LogicError(format!(
+            "Detachment Error: {} is not a Formula::Implies",
+            implication
+        )))
+    }
+}
+
+/// Given a Formula::Equality s=t and a Formula with the Term s at each of the paths given return the Formula with t at those paths. Paths are described by Formula::term_at.
+/// This is an extension of TNT. No Variable of s or t may be quantified above any of the paths.
+/// ```
+/// # use tnt::Formula;
+/// # use std::convert::TryFrom;
+/// # use tnt::substitution;
+/// let f1 = &Formula::try_from("(a+0)=a").unwrap();
+/// let f2 = &Formula::try_from("Eb:(a+0)=Sb").unwrap();
+/// substitution(f1, f2, &[vec![0, 0]]); // Eb:a=Sb
+/// ```
+pub fn substitution(
+    equality: &Formula,
+    formula: &Formula,
+    paths: &[Vec<usize>],
+) -> Result<Formula, LogicError> {
+    let (s, t) = match equality {
+        Formula::Equality(s, t) => (s, t),
         _ => {
             return Err(LogicError(format!(
-                "Transitivity Error: the formulas `{}` and `{}` are not both Formula::Equality",
-                left_formula, right_formula
+                "Substitution Error: {} is not a Formula::Equality",
+                equality
             )))
         }
+    };
+    if paths.is_empty() {
+        return Err(LogicError(
+            "Substitution Error: no paths were given".to_string(),
+        ));
+    }
+    let mut vars = IndexSet::new();
+    s.get_vars(&mut vars);
+    t.get_vars(&mut vars);
+    let mut out = formula.clone();
+    for path in paths {
+        if formula.term_at(path) != Some(s) {
+            return Err(LogicError(format!(
+                "Substitution Error: the Term `{}` is not at the path {:?} in the Formula `{}`",
+                s, path, formula
+            )));
+        }
+        if let Some(v) = formula.bound_along(path).iter().find(|v| vars.contains(*v)) {
+            return Err(LogicError(format!(
+                "Substitution Error: the Term::Variable `{}` is quantified above the path {:?} in the Formula `{}`",
+                v, path, formula
+            )));
+        }
+        *out.term_at_mut(path).unwrap() = t.clone();
+    }
+    Ok(out)
+}
+
+/// A Formula together with the axioms and suppositions it was derived from. Outside of a Deduction a Theorem can only be started from one of the Peano axioms and continued by the methods below, each of which applies one rule of production, so holding a Theorem is proof that its Formula follows from what it records.
+/// ```
+/// # use tnt::{Term, Theorem};
+/// let axiom = Theorem::peano_axiom(1).unwrap();
+/// let t = axiom.specification("a", &Term::one()).unwrap();
+/// assert_eq!(t.formula().to_string(), "(S0+0)=S0");
+/// assert_eq!(t.axioms(), axiom.axioms());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Theorem {
+    formula: Formula,
+    axioms: Vec<Formula>,
+    suppositions: Vec<Formula>,
+}
+
+// Add Formulas to a list, skipping any that are already present.
+fn merge(list: &mut Vec<Formula>, other: &[Formula]) {
+    for f in other {
+        if !list.contains(f) {
+            list.push(f.clone());
+        }
+    }
+}
+
+impl Theorem {
+    /// The Formula that has been proven.
+    pub fn formula(&self) -> &Formula {
+        &self.formula
+    }
+
+    /// The axioms the Theorem depends on.
+    pub fn axioms(&self) -> &[Formula] {
+        &self.axioms
+    }
+
+    /// The premises of suppositions the Theorem depends on that have not been discharged by implication.
+    pub fn suppositions(&self) -> &[Formula] {
+        &self.suppositions
+    }
+
+    // A new Theorem with the dependencies of this one and, if given, another.
+    fn derive(&self, formula: Formula, other: Option<&Theorem>) -> Theorem {
+        let mut out = Theorem {
+            formula,
+            axioms: self.axioms.clone(),
+            suppositions: self.suppositions.clone(),
+        };
+        if let Some(t) = other {
+            merge(&mut out.axioms, &t.axioms);
+            merge(&mut out.suppositions, &t.suppositions);
+        }
+        out
+    }
+
+    // Rename the variables of every Formula, used only to produce an austere Deduction.
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn to_austere_with(&mut self, vars: &IndexSet<String>) {
+        self.formula.to_austere_with(vars);
+        for f in self.axioms.iter_mut().chain(self.suppositions.iter_mut()) {
+            f.to_austere_with(vars);
+        }
+    }
+
+    /// Take one of the Peano axioms, numbered from 0 in the order Deduction::new lists them. The Theorem depends on that axiom.
+    pub fn peano_axiom(n: usize) -> Result<Theorem, LogicError> {
+        match PEANO_AXIOMS.get(n) {
+            Some(axiom) => Ok(Theorem::axiom(axiom)),
+            None => Err(LogicError(format!("Axiom Error: there is no axiom #{n}"))),
+        }
+    }
+
+    // Take a Formula as an axiom, which must be one of the axioms of the Deduction the Theorem is recorded in.
+    pub(crate) fn axiom(formula: &Formula) -> Theorem {
+        Theorem {
+            formula: formula.clone(),
+            axioms: vec![formula.clone()],
+            suppositions: Vec::new(),
+        }
+    }
+
+    // Take a Formula as the premise of a supposition, which only a Deduction can open. The Theorem depends on that premise until it is discharged by implication.
+    pub(crate) fn supposition(premise: &Formula) -> Theorem {
+        Theorem {
+            formula: premise.clone(),
+            axioms: Vec::new(),
+            suppositions: vec![premise.clone()],
+        }
+    }
+
+    /// Discharge a premise and produce the Theorem that it implies the conclusion.
+    pub fn implication(premise: &Formula, conclusion: &Theorem) -> Theorem {
+        let mut out = conclusion.derive(Formula::implies(premise, &conclusion.formula), None);
+        out.suppositions.retain(|f| f != premise);
+        out
+    }
+
+    /// Apply specification, see the function of the same name.
+    pub fn specification(&self, var_name: &str, term: &Term) -> Result<Theorem, LogicError> {
+        Ok(self.derive(specification(&self.formula, var_name, term)?, None))
+    }
+
+    /// Apply generalization, see the function of the same name. The Variable must not be free in any supposition the Theorem depends on.
+    pub fn generalization(&self, var_name: &str) -> Result<Theorem, LogicError> {
+        for premise in self.suppositions.iter() {
+            let mut free_vars = IndexSet::new();
+            premise.get_vars_free(&mut free_vars);
+            if free_vars.contains(var_name) {
+                return Err(LogicError::new(format!(
+                    "Generalization Error: the variable {var_name} is free in the supposition {premise}"
+                )));
+            }
+        }
+        Ok(self.derive(generalization(&self.formula, var_name)?, None))
+    }
+
+    /// Apply existence, see the function of the same name.
+    pub fn existence(&self, var_name: &str) -> Result<Theorem, LogicError> {
+        Ok(self.derive(existence(&self.formula, var_name)?, None))
+    }
+
+    /// Apply interchange_ea, see the function of the same name.
+    pub fn interchange_ea(&self, var_name: &str, n: usize) -> Result<Theorem, LogicError> {
+        Ok(self.derive(interchange_ea(&self.formula, var_name, n)?, None))
+    }
+
+    /// Apply interchange_ae, see the function of the same name.
+    pub fn interchange_ae(&self, var_name: &str, n: usize) -> Result<Theorem, LogicError> {
+        Ok(self.derive(interchange_ae(&self.formula, var_name, n)?, None))
+    }
+
+    /// Apply successor, see the function of the same name.
+    pub fn successor(&self) -> Result<Theorem, LogicError> {
+        Ok(self.derive(successor(&self.formula)?, None))
+    }
+
+    /// Apply predecessor, see the function of the same name.
+    pub fn predecessor(&self) -> Result<Theorem, LogicError> {
+        Ok(self.derive(predecessor(&self.formula)?, None))
+    }
+
+    /// Apply symmetry, see the function of the same name.
+    pub fn symmetry(&self) -> Result<Theorem, LogicError> {
+        Ok(self.derive(symmetry(&self.formula)?, None))
+    }
+
+    /// Apply transitivity with this Theorem on the left, see the function of the same name.
+    pub fn transitivity(&self, right: &Theorem) -> Result<Theorem, LogicError> {
+        let f = transitivity(&self.formula, &right.formula)?;
+        Ok(self.derive(f, Some(right)))
+    }
+
+    /// Apply contrapositive, see the function of the same name.
+    pub fn contrapositive(&self) -> Result<Theorem, LogicError> {
+        Ok(self.derive(contrapositive(&self.formula)?, None))
+    }
+
+    /// Apply detachment with this Theorem as the premise, see the function of the same name.
+    pub fn detachment(&self, implication: &Theorem) -> Result<Theorem, LogicError> {
+        let f = detachment(&self.formula, &implication.formula)?;
+        Ok(self.derive(f, Some(implication)))
+    }
+
+    /// Apply induction, see the function of the same name.
+    pub fn induction(
+        var_name: &str,
+        base: &Theorem,
+        general: &Theorem,
+    ) -> Result<Theorem, LogicError> {
+        let f = induction(var_name, &base.formula, &general.formula)?;
+        Ok(base.derive(f, Some(general)))
+    }
+
+    /// Apply substitution with this Theorem as the equality, see the function of the same name.
+    pub fn substitution(
+        &self,
+        formula: &Theorem,
+        paths: &[Vec<usize>],
+    ) -> Result<Theorem, LogicError> {
+        let f = substitution(&self.formula, &formula.formula, paths)?;
+        Ok(self.derive(f, Some(formula)))
     }
 }
 
@@ -521,6 +801,80 @@ mod test {
         assert!(interchange_ae(formula1, variable, 0).is_err());
     }
 
+    #[test]
+    fn test_contrapositive() -> Result<(), LogicError> {
+        let f = &Formula::try_from("[a=b>Ec:Sa=c]").unwrap();
+        assert_eq!(contrapositive(f)?.to_string(), "[~Ec:Sa=c>~a=b]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_contrapositive_err() {
+        let f = &Formula::try_from("[a=b&Sa=Sb]").unwrap();
+        assert!(contrapositive(f).is_err());
+    }
+
+    #[test]
+    fn test_detachment() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("~a=b").unwrap();
+        let f2 = &Formula::try_from("[~a=b>Aa:a=a]").unwrap();
+        assert_eq!(detachment(f1, f2)?.to_string(), "Aa:a=a");
+        Ok(())
+    }
+
+    #[test]
+    fn test_detachment_err() {
+        let f1 = &Formula::try_from("a=b").unwrap();
+        let f2 = &Formula::try_from("[~a=b>Aa:a=a]").unwrap();
+        let f3 = &Formula::try_from("[a=b|Aa:a=a]").unwrap();
+        assert!(detachment(f1, f2).is_err());
+        assert!(detachment(f1, f3).is_err());
+    }
+
+    #[test]
+    fn test_substitution() -> Result<(), LogicError> {
+        let f1 = &Formula::try_from("(a+0)=a").unwrap();
+        let f2 = &Formula::try_from("Ab:[(a+0)=b>~S(a+0)=(a+0)]").unwrap();
+        let paths = &[vec![0, 1, 0, 0, 0], vec![0, 1, 0, 1]];
+        assert_eq!(
+            substitution(f1, f2, paths)?.to_string(),
+            "Ab:[(a+0)=b>~Sa=a]"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_substitution_err() {
+        let f1 = &Formula::try_from("(a+0)=a").unwrap();
+        let f2 = &Formula::try_from("Aa:(a+0)=a").unwrap();
+        let f3 = &Formula::try_from("(a+0)=a").unwrap();
+        // a is quantified in f2 so the occurrence is not the same Term
+        assert!(substitution(f1, f2, &[vec![0, 0]]).is_err());
+        assert!(substitution(f1, f3, &[vec![1]]).is_err());
+        assert!(substitution(f1, f3, &[]).is_err());
+        assert!(substitution(f2, f3, &[vec![0]]).is_err());
+    }
+
+    #[test]
+    fn test_theorem() -> Result<(), LogicError> {
+        let axiom = Theorem::peano_axiom(1)?;
+        assert_eq!(axiom, Theorem::axiom(&Formula::try_from("Aa:(a+0)=a")?));
+        assert!(Theorem::peano_axiom(5).is_err());
+        let premise = &Formula::try_from("b=S0").unwrap();
+        let supposed = Theorem::supposition(premise);
+        let t = axiom.specification("a", &Term::var("b"))?;
+        let t = t.transitivity(&supposed)?;
+        assert_eq!(t.formula().to_string(), "(b+0)=S0");
+        assert_eq!(t.suppositions(), std::slice::from_ref(premise));
+        assert!(t.generalization("b").is_err());
+        let t = Theorem::implication(premise, &t);
+        assert_eq!(t.formula().to_string(), "[b=S0>(b+0)=S0]");
+        assert!(t.suppositions().is_empty());
+        assert_eq!(t.axioms(), axiom.axioms());
+        assert!(t.generalization("b").is_ok());
+        Ok(())
+    }
+
     #[test]
     fn test_induction() -> Result<(), LogicError> {
         let v = "v";
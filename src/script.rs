@@ -0,0 +1,388 @@
+//! A line oriented text format for storing a Deduction.
+//!
+//! Each line holds one step: the name of a rule followed by its arguments separated by spaces. A step may end with `;` and the Formula it is expected to produce, which is checked when the script is read. Suppositions are opened with `begin` followed by the premise and closed with `end`. A `#` at the start of a line or after a space begins a comment and blank lines are ignored.
+//! ```text
+//! title One Plus One Equals Two
+//! axiom 2 ; Aa:Ab:(a+Sb)=S(a+b)
+//! specification 0 a S0 ; Ab:(S0+Sb)=S(S0+b)
+//! begin S0=0        # never true
+//!     symmetry 2 ; 0=S0
+//! end ; [S0=0>0=S0]
+//! ```
+//! The title line is optional and its title is the rest of the line, so it may contain `#`. A Deduction with axioms other than the Peano axioms lists them first with `custom-axiom` followed by the Formula, or with the single line `no-axioms` if it has none.
+//!
+//! The steps are written `axiom n`, `specification n var term`, `generalization n var`, `existence n var`, `successor n`, `predecessor n`, `interchange-ae n var position`, `interchange-ea n var position`, `symmetry n`, `transitivity n1 n2`, `contrapositive n`, `detachment premise implication`, `induction var base general` and `substitution equality theorem path...` where each path is written with its steps separated by `.` as in `0.1.0`.
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{Error, Write};
+
+use crate::deduction::{Justification, PEANO_AXIOMS};
+use crate::{Deduction, Formula, LogicError, Term};
+
+fn script_error(line: usize, message: &str) -> LogicError {
+    LogicError(format!("Script Error: line {line}: {message}"))
+}
+
+// Remove a comment, which begins with a `#` at the start of the line or after whitespace.
+fn strip_comment(line: &str) -> &str {
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        if c == '#' && prev.is_whitespace() {
+            return line[..i].trim_end();
+        }
+        prev = c;
+    }
+    line
+}
+
+// The text of a step as it appears in a script, without the expected Formula.
+fn step_string(justification: &Justification, formula: &Formula) -> String {
+    match justification {
+        Justification::Axiom(n) => format!("axiom {n}"),
+        Justification::Specification {
+            theorem,
+            var_name,
+            term,
+        } => format!("specification {theorem} {var_name} {term}"),
+        Justification::Generalization { theorem, var_name } => {
+            format!("generalization {theorem} {var_name}")
+        }
+        Justification::Existence { theorem, var_name } => {
+            format!("existence {theorem} {var_name}")
+        }
+        Justification::Successor(n) => format!("successor {n}"),
+        Justification::Predecessor(n) => format!("predecessor {n}"),
+        Justification::InterchangeAE {
+            theorem,
+            var_name,
+            position,
+        } => format!("interchange-ae {theorem} {var_name} {position}"),
+        Justification::InterchangeEA {
+            theorem,
+            var_name,
+            position,
+        } => format!("interchange-ea {theorem} {var_name} {position}"),
+        Justification::Symmetry(n) => format!("symmetry {n}"),
+        Justification::Transitivity(n1, n2) => format!("transitivity {n1} {n2}"),
+        Justification::Supposition => format!("begin {formula}"),
+        Justification::Implication { .. } => "end".to_string(),
+        Justification::Induction {
+            var_name,
+            base,
+            general,
+        } => format!("induction {var_name} {base} {general}"),
+        Justification::Contrapositive(n) => format!("contrapositive {n}"),
+        Justification::Detachment {
+            premise,
+            implication,
+        } => format!("detachment {premise} {implication}"),
+        Justification::Substitution {
+            equality,
+            theorem,
+            paths,
+        } => {
+            let paths: Vec<String> = paths
+                .iter()
+                .map(|p| {
+                    p.iter()
+                        .map(|i| i.to_string())
+                        .collect::<Vec<String>>()
+                        .join(".")
+                })
+                .collect();
+            format!("substitution {equality} {theorem} {}", paths.join(" "))
+        }
+    }
+}
+
+// Read the arguments of a step into a Justification.
+fn parse_step(rule: &str, args: &[&str]) -> Result<Justification, String> {
+    let num = |i: usize| -> Result<usize, String> {
+        let arg = args
+            .get(i)
+            .ok_or(format!("`{rule}` is missing an argument"))?;
+        arg.parse::<usize>()
+            .map_err(|_| format!("`{arg}` is not a theorem number"))
+    };
+    let word = |i: usize| -> Result<String, String> {
+        args.get(i)
+            .map(|s| s.to_string())
+            .ok_or(format!("`{rule}` is missing an argument"))
+    };
+    let arity = match rule {
+        "axiom" | "successor" | "predecessor" | "symmetry" | "contrapositive" => Some(1),
+        "generalization" | "existence" | "transitivity" | "detachment" => Some(2),
+        "specification" | "interchange-ae" | "interchange-ea" | "induction" => Some(3),
+        _ => None,
+    };
+    if let Some(n) = arity {
+        if args.len() != n {
+            return Err(format!("`{rule}` takes {n} arguments"));
+        }
+    }
+    Ok(match rule {
+        "axiom" => Justification::Axiom(num(0)?),
+        "specification" => Justification::Specification {
+            theorem: num(0)?,
+            var_name: word(1)?,
+            term: Term::try_from(args[2]).map_err(|e| e.to_string())?,
+        },
+        "generalization" => Justification::Generalization {
+            theorem: num(0)?,
+            var_name: word(1)?,
+        },
+        "existence" => Justification::Existence {
+            theorem: num(0)?,
+            var_name: word(1)?,
+        },
+        "successor" => Justification::Successor(num(0)?),
+        "predecessor" => Justification::Predecessor(num(0)?),
+        "interchange-ae" => Justification::InterchangeAE {
+            theorem: num(0)?,
+            var_name: word(1)?,
+            position: num(2)?,
+        },
+        "interchange-ea" => Justification::InterchangeEA {
+            theorem: num(0)?,
+            var_name: word(1)?,
+            position: num(2)?,
+        },
+        "symmetry" => Justification::Symmetry(num(0)?),
+        "transitivity" => Justification::Transitivity(num(0)?, num(1)?),
+        "contrapositive" => Justification::Contrapositive(num(0)?),
+        "detachment" => Justification::Detachment {
+            premise: num(0)?,
+            implication: num(1)?,
+        },
+        "induction" => Justification::Induction {
+            var_name: word(0)?,
+            base: num(1)?,
+            general: num(2)?,
+        },
+        "substitution" => {
+            let mut paths = Vec::new();
+            for arg in args.iter().skip(2) {
+                let path: Result<Vec<usize>, _> = arg.split('.').map(|i| i.parse()).collect();
+                paths.push(path.map_err(|_| format!("`{arg}` is not a path"))?);
+            }
+            Justification::Substitution {
+                equality: num(0)?,
+                theorem: num(1)?,
+                paths,
+            }
+        }
+        _ => return Err(format!("`{rule}` is not a rule")),
+    })
+}
+
+impl Deduction {
+    /// Write the Deduction in the script format described in the module documentation. Every step includes the Formula it produces.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// let mut d = Deduction::new("Example");
+    /// d.add_axiom(1).unwrap();
+    /// d.specification(0, "a", &Term::one()).unwrap();
+    /// assert_eq!(
+    ///     d.to_script(),
+    ///     "title Example\naxiom 1 ; Aa:(a+0)=a\nspecification 0 a S0 ; (S0+0)=S0\n"
+    /// );
+    /// ```
+    pub fn to_script(&self) -> String {
+        let mut out = format!("title {}\n", self.title);
+        if self.axioms().is_empty() {
+            out.push_str("no-axioms\n");
+        } else if self.axioms() != PEANO_AXIOMS.as_slice() {
+            for axiom in self.axioms() {
+                out.push_str(&format!("custom-axiom {axiom}\n"));
+            }
+        }
+        for t in self.theorems() {
+            let indent = match t.justification {
+                Justification::Supposition => t.depth - 1,
+                _ => t.depth,
+            };
+            let step = step_string(&t.justification, t.formula());
+            out.push_str(&"    ".repeat(indent));
+            match t.justification {
+                Justification::Supposition => out.push_str(&format!("{step}\n")),
+                _ => out.push_str(&format!("{step} ; {}\n", t.formula())),
+            }
+        }
+        out
+    }
+
+    /// Read a Deduction from the script format described in the module documentation. Every step is checked as it is applied and any expected Formula must match.
+    /// ```
+    /// # use tnt::Deduction;
+    /// let d = Deduction::from_script("axiom 1\nspecification 0 a S0 ; (S0+0)=S0").unwrap();
+    /// assert_eq!(d.last_theorem().formula().to_string(), "(S0+0)=S0");
+    /// ```
+    pub fn from_script(script: &str) -> Result<Deduction, LogicError> {
+        let mut title = String::new();
+        let mut axioms = Vec::new();
+        let mut custom = false;
+        let mut deduction: Option<Deduction> = None;
+        for (i, line) in script.lines().enumerate() {
+            let line_num = i + 1;
+            let line = line.trim();
+            let line = match line.split_once(' ') {
+                Some(("title", _)) => line,
+                _ => strip_comment(line),
+            };
+            if line.is_empty() {
+                continue;
+            }
+            let (rule, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+
+            // Directives that describe the Deduction must come before the first step
+            if rule == "title" || rule == "custom-axiom" || rule == "no-axioms" {
+                if deduction.is_some() {
+                    return Err(script_error(line_num, "must come before the first step"));
+                }
+                if rule == "title" {
+                    title = rest.to_string();
+                } else if rule == "no-axioms" {
+                    if !rest.is_empty() || !axioms.is_empty() {
+                        return Err(script_error(
+                            line_num,
+                            "`no-axioms` takes no arguments and cannot follow `custom-axiom`",
+                        ));
+                    }
+                    custom = true;
+                } else {
+                    if custom {
+                        return Err(script_error(
+                            line_num,
+                            "`custom-axiom` cannot follow `no-axioms`",
+                        ));
+                    }
+                    let f = Formula::try_from(rest).map_err(|e| script_error(line_num, &e.0))?;
+                    axioms.push(f);
+                }
+                continue;
+            }
+            let d = deduction.get_or_insert_with(|| {
+                if axioms.is_empty() && !custom {
+                    Deduction::new(&title)
+                } else {
+                    Deduction::custom(&title, axioms.clone())
+                }
+            });
+
+            let (step, expected) = match rest.split_once(';') {
+                Some((step, expected)) => (step.trim(), Some(expected.trim())),
+                None => (rest, None),
+            };
+            let result = match rule {
+                "begin" => Formula::try_from(step).and_then(|f| d.supposition(f)),
+                "end" if !step.is_empty() => Err(LogicError("`end` takes no arguments".into())),
+                "end" if d.depth() == 0 => Err(LogicError("there is no supposition to end".into())),
+                "end" => d.implication(),
+                _ => {
+                    let args: Vec<&str> = step.split_whitespace().collect();
+                    let j = parse_step(rule, &args).map_err(LogicError)?;
+                    d.apply(&j, None)
+                }
+            };
+            result.map_err(|e| script_error(line_num, &e.0))?;
+
+            if let Some(expected) = expected {
+                let f = Formula::try_from(expected).map_err(|e| script_error(line_num, &e.0))?;
+                if d.last_theorem().formula() != &f {
+                    return Err(script_error(
+                        line_num,
+                        &format!(
+                            "the step gives `{}` rather than `{f}`",
+                            d.last_theorem().formula()
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(deduction.unwrap_or_else(|| {
+            if axioms.is_empty() && !custom {
+                Deduction::new(&title)
+            } else {
+                Deduction::custom(&title, axioms)
+            }
+        }))
+    }
+
+    /// Create a script file with the given file name that stores the Deduction.
+    pub fn script_file(&self, filename: &str) -> Result<(), Error> {
+        let filename = format!("{}.tnt", filename);
+        let mut file = File::create(filename)?;
+        file.write_all(self.to_script().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{check, lemmas};
+
+    fn assert_round_trip(script: &str) -> Result<(), LogicError> {
+        let d = Deduction::from_script(script)?;
+        check(&d)?;
+        let written = d.to_script();
+        let again = Deduction::from_script(&written)?;
+        assert_eq!(written, again.to_script());
+        assert_eq!(d.theorems().len(), again.theorems().len());
+        for (t1, t2) in d.theorems().zip(again.theorems()) {
+            assert_eq!(t1.formula(), t2.formula());
+            assert_eq!(t1.justification, t2.justification);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_examples() -> Result<(), LogicError> {
+        assert_round_trip(include_str!("../examples/addition.tnt"))?;
+        assert_round_trip(include_str!("../examples/commutativity.tnt"))?;
+        let d = Deduction::from_script(include_str!("../examples/commutativity.tnt"))?;
+        assert_eq!(d.last_theorem().formula().to_string(), "Ad:Ac:(c+d)=(d+c)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), LogicError> {
+        let (d, _) = lemmas::distributivity()?;
+        assert_round_trip(&d.to_script())?;
+        let mut d = Deduction::custom("Custom", vec![Formula::try_from("Aa:a=a").unwrap()]);
+        d.add_axiom(0)?;
+        d.specification(0, "a", &Term::zero())?;
+        d.supposition(Formula::try_from("0=S0").unwrap())?;
+        d.substitution(2, 1, &[vec![0]])?;
+        d.implication()?;
+        assert_round_trip(&d.to_script())?;
+
+        // a title may contain `#` and a Deduction may have no axioms at all
+        let d = Deduction::custom("Issue #3", vec![]);
+        let read = Deduction::from_script(&d.to_script())?;
+        assert_eq!(read.title, "Issue #3");
+        assert!(read.axioms().is_empty());
+        let read = Deduction::from_script("title Empty\n")?;
+        assert_eq!(read.axioms(), PEANO_AXIOMS.as_slice());
+        let read = Deduction::from_script("axiom 1 # ; 0=0")?;
+        assert_eq!(read.theorems().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_script_err() {
+        // the expected Formula is wrong
+        assert!(Deduction::from_script("axiom 1 ; Aa:(a+0)=0").is_err());
+        assert!(Deduction::from_script("axiom 7").is_err());
+        assert!(Deduction::from_script("symmetry 0").is_err());
+        assert!(Deduction::from_script("axiom 1\nspecification 0 a").is_err());
+        assert!(Deduction::from_script("axiom 1\nrotation 0").is_err());
+        assert!(Deduction::from_script("end").is_err());
+        assert!(Deduction::from_script("axiom 1\ntitle Late").is_err());
+        // only a `#` after a space begins a comment
+        assert!(Deduction::from_script("axiom 1\nsuccessor 0#1").is_err());
+        assert!(Deduction::from_script("no-axioms\ncustom-axiom Aa:a=a").is_err());
+    }
+}
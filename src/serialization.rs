@@ -0,0 +1,208 @@
+//! Serialization with serde, available with the `serde` feature.
+//!
+//! Terms and Formulas are stored as their text. A Deduction is stored as its title, axioms, scopes and the lines it contains but is never trusted when read back. Every line is derived again from its Justification, as by `check`, and reading fails if any of them does not match.
+//!
+//! A TheoremFrame can be serialized on its own but is only read back as part of its Deduction, since the lines it was derived from are needed to check it.
+//! ```
+//! # use tnt::{Deduction, Term};
+//! let mut d = Deduction::new("Stored");
+//! d.add_axiom(1).unwrap();
+//! d.specification(0, "a", &Term::one()).unwrap();
+//! let json = serde_json::to_string(&d).unwrap();
+//! let e: Deduction = serde_json::from_str(&json).unwrap();
+//! assert_eq!(e.last_theorem().formula().to_string(), "(S0+0)=S0");
+//! ```
+
+use std::convert::TryFrom;
+
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::check::{replay, Line};
+use crate::deduction::{Justification, TheoremFrame};
+use crate::{Deduction, Formula, LogicError, Term};
+
+impl Serialize for Term {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Term {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        // The parser accepts any text that begins with a Term so it must be compared to the original
+        match Term::try_from(text.as_str()) {
+            Ok(t) if t.to_string() == text => Ok(t),
+            _ => Err(de::Error::custom(format!(
+                "`{text}` is not a well formed Term"
+            ))),
+        }
+    }
+}
+
+impl Serialize for Formula {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Formula {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        match Formula::try_from(text.as_str()) {
+            Ok(f) if f.to_string() == text => Ok(f),
+            _ => Err(de::Error::custom(format!(
+                "`{text}` is not a well formed Formula"
+            ))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct FrameRecord {
+    formula: Formula,
+    depth: usize,
+    position: usize,
+    justification: Justification,
+    scope: usize,
+}
+
+impl From<&TheoremFrame> for FrameRecord {
+    fn from(frame: &TheoremFrame) -> Self {
+        FrameRecord {
+            formula: frame.formula().clone(),
+            depth: frame.depth,
+            position: frame.position,
+            justification: frame.justification.clone(),
+            scope: frame.scope,
+        }
+    }
+}
+
+impl Serialize for TheoremFrame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FrameRecord::from(self).serialize(serializer)
+    }
+}
+
+impl<'a> From<&'a FrameRecord> for Line<'a> {
+    fn from(record: &'a FrameRecord) -> Self {
+        Line {
+            position: record.position,
+            formula: &record.formula,
+            justification: &record.justification,
+            depth: record.depth,
+            scope: record.scope,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DeductionRecord {
+    title: String,
+    axioms: Vec<Formula>,
+    scope_stack: Vec<usize>,
+    scope_cur: usize,
+    theorems: Vec<FrameRecord>,
+}
+
+impl TryFrom<DeductionRecord> for Deduction {
+    type Error = LogicError;
+
+    fn try_from(record: DeductionRecord) -> Result<Self, Self::Error> {
+        let lines = record.theorems.iter().map(Line::from);
+        let d = replay(&record.title, record.axioms, lines)?;
+        if d.scopes() != (record.scope_stack.as_slice(), record.scope_cur) {
+            return Err(LogicError(
+                "Check Error: the recorded scopes do not match the lines of the Deduction"
+                    .to_string(),
+            ));
+        }
+        Ok(d)
+    }
+}
+
+impl Serialize for Deduction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (scope_stack, scope_cur) = self.scopes();
+        DeductionRecord {
+            title: self.title.clone(),
+            axioms: self.axioms().to_vec(),
+            scope_stack: scope_stack.to_vec(),
+            scope_cur,
+            theorems: self.theorems().map(FrameRecord::from).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Deduction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = DeductionRecord::deserialize(deserializer)?;
+        Deduction::try_from(record).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lemmas;
+
+    #[test]
+    fn test_serde() -> Result<(), LogicError> {
+        let f = Formula::try_from("Aa:[a=b>Eb':~b'=Sa]")?;
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, "\"Aa:[a=b>Eb':~b'=Sa]\"");
+        assert_eq!(serde_json::from_str::<Formula>(&json).unwrap(), f);
+
+        let (d, _) = lemmas::addition_cancels()?;
+        let json = serde_json::to_string(&d).unwrap();
+        let e: Deduction = serde_json::from_str(&json).unwrap();
+        assert_eq!(e.title, d.title);
+        assert_eq!(e.theorems().len(), d.theorems().len());
+        for (t1, t2) in d.theorems().zip(e.theorems()) {
+            assert_eq!(t1.formula(), t2.formula());
+            assert_eq!(t1.justification, t2.justification);
+        }
+        assert_eq!(json, serde_json::to_string(&e).unwrap());
+        // a frame is stored as it is within its Deduction
+        let frame = serde_json::to_string(d.theorem(0)).unwrap();
+        assert!(json.contains(&frame));
+
+        // an open supposition is kept
+        let mut d = Deduction::new("Open");
+        d.supposition(Formula::try_from("a=b")?)?;
+        d.symmetry(0)?;
+        let json = serde_json::to_string(&d).unwrap();
+        let mut e: Deduction = serde_json::from_str(&json).unwrap();
+        assert_eq!(e.depth(), 1);
+        e.implication()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_err() -> Result<(), LogicError> {
+        assert!(serde_json::from_str::<Term>("\"S0junk\"").is_err());
+        assert!(serde_json::from_str::<Formula>("\"a=\"").is_err());
+
+        let mut d = Deduction::new("Tampered");
+        d.add_axiom(1)?;
+        d.specification(0, "a", &Term::one())?;
+        let json = serde_json::to_string(&d).unwrap();
+        assert!(serde_json::from_str::<Deduction>(&json).is_ok());
+        // a formula that does not follow from the justification
+        let forged = json.replace("(S0+0)=S0", "(S0+0)=0");
+        assert!(serde_json::from_str::<Deduction>(&forged).is_err());
+        // a different axiom system
+        let forged = json.replace("Aa:(a+0)=a", "Aa:(a+0)=0");
+        assert!(serde_json::from_str::<Deduction>(&forged).is_err());
+        // scopes that do not match the lines
+        let forged = json.replace("\"scope_stack\":[0]", "\"scope_stack\":[0,1]");
+        assert_ne!(forged, json);
+        assert!(serde_json::from_str::<Deduction>(&forged).is_err());
+        Ok(())
+    }
+}
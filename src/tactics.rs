@@ -0,0 +1,600 @@
+//! Tactics extend a Deduction with complete derivations of routine facts. Every step is made with the ordinary rules of production so the result is checked exactly as if it were written out by hand. Each tactic returns the position of the theorem it proves.
+//!
+//! The congruence tactics rely on lemmas such as Aa:Ab:Ac:[a=b>(a+c)=(b+c)]. Each lemma is proven by induction the first time it is needed and afterward only specified.
+
+use num::ToPrimitive;
+use std::collections::HashMap;
+
+use crate::decision::eval_term;
+use crate::deduction::PEANO_AXIOMS;
+use crate::{production::*, Deduction, Formula, LogicError, Term};
+
+// Wrap a Term in one Term::Successor for each Term::Successor in the numeral n.
+fn with_successors(term: &Term, n: &Term) -> Term {
+    match n {
+        Term::Successor(inner) => Term::succ(&with_successors(term, inner)),
+        _ => term.clone(),
+    }
+}
+
+// The value of a Term without Variables, small enough to be written as a numeral.
+fn numeral_value(term: &Term) -> Result<usize, LogicError> {
+    let value = eval_term(term, &HashMap::new()).map_err(|_| {
+        LogicError(format!(
+            "Tactic Error: the Term `{term}` contains a Term::Variable and has no value"
+        ))
+    })?;
+    value.to_usize().ok_or_else(|| {
+        LogicError(format!(
+            "Tactic Error: the value of the Term `{term}` is too large to write as a numeral"
+        ))
+    })
+}
+
+// Number of Term::Successor wrapped around the innermost Term.
+fn successor_count(term: &Term) -> usize {
+    match term {
+        Term::Successor(inner) => 1 + successor_count(inner),
+        _ => 0,
+    }
+}
+
+impl Deduction {
+    // Position of the most recent accessible theorem that is exactly the given Formula.
+    pub(crate) fn find_theorem(&self, formula: &Formula) -> Option<usize> {
+        self.theorems()
+            .rev()
+            .find(|t| t.formula() == formula && self.get_theorem(t.position).is_ok())
+            .map(|t| t.position)
+    }
+
+    // Position of an accessible copy of one of the standard axioms, adding it if needed.
+    pub(crate) fn peano_axiom(&mut self, n: usize) -> Result<usize, LogicError> {
+        let axiom = &PEANO_AXIOMS[n];
+        if self.axioms().get(n) != Some(axiom) {
+            return Err(LogicError(format!(
+                "Tactic Error: axiom #{n} of the Deduction is not `{axiom}`"
+            )));
+        }
+        match self.find_theorem(axiom) {
+            Some(pos) => Ok(pos),
+            None => {
+                self.add_axiom(n)?;
+                Ok(self.last_theorem().position)
+            }
+        }
+    }
+
+    // Specify variables of theorem n in order, reusing any step that already exists.
+    pub(crate) fn specify_all(
+        &mut self,
+        mut n: usize,
+        terms: &[(&str, &Term)],
+    ) -> Result<usize, LogicError> {
+        for (var_name, term) in terms {
+            let t = specification(self.get_theorem(n)?, var_name, term)?;
+            n = match self.find_theorem(&t) {
+                Some(pos) => pos,
+                None => {
+                    self.specification(n, var_name, term)?;
+                    self.last_theorem().position
+                }
+            };
+        }
+        Ok(n)
+    }
+
+    // Apply successor to theorem n the given number of times, reusing any step that already exists.
+    pub(crate) fn successor_times(
+        &mut self,
+        mut n: usize,
+        times: usize,
+    ) -> Result<usize, LogicError> {
+        for _ in 0..times {
+            let t = successor(self.get_theorem(n)?)?;
+            n = match self.find_theorem(&t) {
+                Some(pos) => pos,
+                None => {
+                    self.successor(n)?;
+                    self.last_theorem().position
+                }
+            };
+        }
+        Ok(n)
+    }
+
+    // Transitivity of theorems n1 and n2, reusing the result if it already exists.
+    pub(crate) fn transitivity_reuse(&mut self, n1: usize, n2: usize) -> Result<usize, LogicError> {
+        let t = transitivity(self.get_theorem(n1)?, self.get_theorem(n2)?)?;
+        match self.find_theorem(&t) {
+            Some(pos) => Ok(pos),
+            None => {
+                self.transitivity(n1, n2)?;
+                Ok(self.last_theorem().position)
+            }
+        }
+    }
+
+    // Symmetry of theorem n, reusing the result if it already exists.
+    pub(crate) fn symmetry_reuse(&mut self, n: usize) -> Result<usize, LogicError> {
+        let t = symmetry(self.get_theorem(n)?)?;
+        match self.find_theorem(&t) {
+            Some(pos) => Ok(pos),
+            None => {
+                self.symmetry(n)?;
+                Ok(self.last_theorem().position)
+            }
+        }
+    }
+
+    // Detachment of theorem n1 from theorem n2, reusing the result if it already exists.
+    pub(crate) fn detachment_reuse(&mut self, n1: usize, n2: usize) -> Result<usize, LogicError> {
+        let t = detachment(self.get_theorem(n1)?, self.get_theorem(n2)?)?;
+        match self.find_theorem(&t) {
+            Some(pos) => Ok(pos),
+            None => {
+                self.detachment(n1, n2)?;
+                Ok(self.last_theorem().position)
+            }
+        }
+    }
+
+    // Prove (x+n)=S..Sx, with one S for each in the numeral n, for any Term x without free Variables.
+    fn sum_with_numeral(&mut self, x: &Term, n: &Term) -> Result<usize, LogicError> {
+        let goal = Formula::eq(&Term::sum(x, n), &with_successors(x, n));
+        if let Some(pos) = self.find_theorem(&goal) {
+            return Ok(pos);
+        }
+        match n {
+            Term::Successor(inner) => {
+                let ax = self.peano_axiom(2)?;
+                let step = self.specify_all(ax, &[("a", x), ("b", inner)])?;
+                let prev = self.sum_with_numeral(x, inner)?;
+                let prev = self.successor_times(prev, 1)?;
+                self.transitivity_reuse(step, prev)
+            }
+            _ => {
+                let ax = self.peano_axiom(1)?;
+                self.specify_all(ax, &[("a", x)])
+            }
+        }
+    }
+
+    /// Push a derivation of (m+n)=k where m and n are numerals and k is the numeral of their sum. Only the Peano axioms and the rules of production are used.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Two Plus Three Equals Five");
+    /// let two = &Term::try_from("SS0").unwrap();
+    /// let three = &Term::try_from("SSS0").unwrap();
+    /// let n = d.numeral_sum(two, three).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(SS0+SSS0)=SSSSS0");
+    /// ```
+    pub fn numeral_sum(&mut self, m: &Term, n: &Term) -> Result<usize, LogicError> {
+        if !m.is_num() || !n.is_num() {
+            return Err(LogicError(format!(
+                "Tactic Error: the Terms `{m}` and `{n}` are not both numerals"
+            )));
+        }
+        self.sum_with_numeral(m, n)
+    }
+
+    /// Push a derivation of (m*n)=k where m and n are numerals and k is the numeral of their product. Only the Peano axioms and the rules of production are used.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Two Times Three Equals Six");
+    /// let two = &Term::try_from("SS0").unwrap();
+    /// let three = &Term::try_from("SSS0").unwrap();
+    /// let n = d.numeral_product(two, three).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(SS0*SSS0)=SSSSSS0");
+    /// ```
+    pub fn numeral_product(&mut self, m: &Term, n: &Term) -> Result<usize, LogicError> {
+        if !m.is_num() || !n.is_num() {
+            return Err(LogicError(format!(
+                "Tactic Error: the Terms `{m}` and `{n}` are not both numerals"
+            )));
+        }
+        match n {
+            Term::Successor(inner) => {
+                // (m*Sn)=((m*n)+m) and ((m*n)+m)=S..S(m*n) then replace (m*n) with its value
+                let ax = self.peano_axiom(4)?;
+                let step = self.specify_all(ax, &[("a", m), ("b", inner)])?;
+                let prev = self.numeral_product(m, inner)?;
+                let expand = self.sum_with_numeral(&Term::prod(m, inner), m)?;
+                let step = self.transitivity_reuse(step, expand)?;
+                let value = self.successor_times(prev, successor_count(m))?;
+                self.transitivity_reuse(step, value)
+            }
+            _ => {
+                let ax = self.peano_axiom(3)?;
+                self.specify_all(ax, &[("a", m)])
+            }
+        }
+    }
+
+    // Prove t=k where k is the numeral with the value of the Term t. Returns None when t is already a numeral.
+    // A right operand that is not a numeral is first evaluated and then replaced using the congruence lemmas.
+    fn evaluate(&mut self, term: &Term) -> Result<Option<usize>, LogicError> {
+        if term.is_num() {
+            return Ok(None);
+        }
+        let value = numeral_value(term)?;
+        if let Some(pos) = self.find_theorem(&Formula::eq(term, &Term::numeral(value))) {
+            return Ok(Some(pos));
+        }
+        let pos = match term {
+            Term::Successor(inner) => {
+                let prev = self.evaluate(inner)?.unwrap();
+                self.successor_times(prev, 1)?
+            }
+            Term::Sum(x, y) if y.is_num() => {
+                let step = self.sum_with_numeral(x, y)?;
+                match self.evaluate(x)? {
+                    Some(prev) => {
+                        let value = self.successor_times(prev, successor_count(y))?;
+                        self.transitivity_reuse(step, value)?
+                    }
+                    None => step,
+                }
+            }
+            Term::Sum(x, y) => match &**y {
+                Term::Successor(inner) => {
+                    let ax = self.peano_axiom(2)?;
+                    let step = self.specify_all(ax, &[("a", x), ("b", inner)])?;
+                    let prev = self.evaluate(&Term::sum(x, inner))?.unwrap();
+                    let value = self.successor_times(prev, 1)?;
+                    self.transitivity_reuse(step, value)?
+                }
+                _ => {
+                    let prev = self.evaluate(y)?.unwrap();
+                    let step = self.sum_congruence_right(prev, x)?;
+                    let value = self
+                        .evaluate(&Term::sum(x, &self.numeral_of(prev)))?
+                        .unwrap();
+                    self.transitivity_reuse(step, value)?
+                }
+            },
+            Term::Product(x, y) if x.is_num() && y.is_num() => self.numeral_product(x, y)?,
+            Term::Product(x, y) => match &**y {
+                Term::Zero => {
+                    let ax = self.peano_axiom(3)?;
+                    self.specify_all(ax, &[("a", x)])?
+                }
+                Term::Successor(inner) => {
+                    let ax = self.peano_axiom(4)?;
+                    let step = self.specify_all(ax, &[("a", x), ("b", inner)])?;
+                    let prev = self
+                        .evaluate(&Term::sum(&Term::prod(x, inner), x))?
+                        .unwrap();
+                    self.transitivity_reuse(step, prev)?
+                }
+                _ => {
+                    let prev = self.evaluate(y)?.unwrap();
+                    let step = self.product_congruence_right(prev, x)?;
+                    let value = self
+                        .evaluate(&Term::prod(x, &self.numeral_of(prev)))?
+                        .unwrap();
+                    self.transitivity_reuse(step, value)?
+                }
+            },
+            Term::Variable(_) | Term::Zero => {
+                unreachable!("the Term has a value and is not a numeral")
+            }
+        };
+        Ok(Some(pos))
+    }
+
+    /// Push a derivation of t=k where t is any Term without Variables and k is the numeral of its value. Steps and lemmas already in the Deduction are reused rather than derived again.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Normalization");
+    /// let t = &Term::try_from("(S0*(S0+S0))").unwrap();
+    /// let n = d.normalize(t).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(S0*(S0+S0))=SS0");
+    /// ```
+    pub fn normalize(&mut self, term: &Term) -> Result<usize, LogicError> {
+        match self.evaluate(term)? {
+            Some(pos) => Ok(pos),
+            None => {
+                // A numeral is equal to itself by way of (k+0)=k
+                let ax = self.peano_axiom(1)?;
+                let step = self.specify_all(ax, &[("a", term)])?;
+                let flip = self.symmetry_reuse(step)?;
+                self.transitivity_reuse(flip, step)
+            }
+        }
+    }
+
+    /// Given theorem n of the form s=t push a derivation of (s+r)=(t+r).
+    /// ```
+    /// # use tnt::{Deduction, Formula, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Congruence");
+    /// d.supposition(Formula::try_from("a=b").unwrap()).unwrap();
+    /// let n = d.sum_congruence_left(0, &Term::var("c")).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(a+c)=(b+c)");
+    /// ```
+    pub fn sum_congruence_left(&mut self, n: usize, r: &Term) -> Result<usize, LogicError> {
+        self.congruence(n, r, Deduction::sum_congruence_left_lemma)
+    }
+
+    /// Given theorem n of the form s=t push a derivation of (r+s)=(r+t).
+    /// ```
+    /// # use tnt::{Deduction, Formula, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Congruence");
+    /// d.supposition(Formula::try_from("a=b").unwrap()).unwrap();
+    /// let n = d.sum_congruence_right(0, &Term::var("c")).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(c+a)=(c+b)");
+    /// ```
+    pub fn sum_congruence_right(&mut self, n: usize, r: &Term) -> Result<usize, LogicError> {
+        self.congruence(n, r, Deduction::sum_congruence_right_lemma)
+    }
+
+    /// Given theorem n of the form s=t push a derivation of (s*r)=(t*r).
+    /// ```
+    /// # use tnt::{Deduction, Formula, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Congruence");
+    /// d.supposition(Formula::try_from("a=b").unwrap()).unwrap();
+    /// let n = d.product_congruence_left(0, &Term::var("c")).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(a*c)=(b*c)");
+    /// ```
+    pub fn product_congruence_left(&mut self, n: usize, r: &Term) -> Result<usize, LogicError> {
+        self.congruence(n, r, Deduction::product_congruence_left_lemma)
+    }
+
+    /// Given theorem n of the form s=t push a derivation of (r*s)=(r*t).
+    /// ```
+    /// # use tnt::{Deduction, Formula, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Congruence");
+    /// d.supposition(Formula::try_from("a=b").unwrap()).unwrap();
+    /// let n = d.product_congruence_right(0, &Term::var("c")).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "(c*a)=(c*b)");
+    /// ```
+    pub fn product_congruence_right(&mut self, n: usize, r: &Term) -> Result<usize, LogicError> {
+        self.congruence(n, r, Deduction::product_congruence_right_lemma)
+    }
+
+    /// Push a derivation of ~s=t where s and t are Terms without Variables that have different values. The Peano axioms and the rules of production, including contrapositive and detachment, are used.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// # use std::convert::TryFrom;
+    /// let mut d = Deduction::new("Two Is Not Three");
+    /// let two = &Term::try_from("SS0").unwrap();
+    /// let three = &Term::try_from("SSS0").unwrap();
+    /// let n = d.inequality(two, three).unwrap();
+    /// assert_eq!(d.theorem(n).formula().to_string(), "~SS0=SSS0");
+    /// ```
+    pub fn inequality(&mut self, s: &Term, t: &Term) -> Result<usize, LogicError> {
+        // Nothing is pushed unless the values differ
+        let (m, n) = (numeral_value(s)?, numeral_value(t)?);
+        if m == n {
+            return Err(LogicError(format!(
+                "Tactic Error: the Terms `{s}` and `{t}` are both equal to `{}`",
+                Term::numeral(m)
+            )));
+        }
+        self.atomic(|d| d.inequality_steps(s, t, Term::numeral(m), Term::numeral(n)))
+    }
+
+    // Derive ~s=t where s and t have the distinct values m and n.
+    fn inequality_steps(
+        &mut self,
+        s: &Term,
+        t: &Term,
+        m: Term,
+        n: Term,
+    ) -> Result<usize, LogicError> {
+        let s_eval = self.evaluate(s)?;
+        let t_eval = self.evaluate(t)?;
+        let s_reversed = match s_eval {
+            Some(pos) => {
+                self.symmetry(pos)?;
+                Some(self.last_theorem().position)
+            }
+            None => None,
+        };
+
+        // Suppose s=t then replace each side with its value to get m=n
+        self.supposition(Formula::eq(s, t))?;
+        if let Some(pos) = s_reversed {
+            self.transitivity(pos, self.last_theorem().position)?;
+        }
+        if let Some(pos) = t_eval {
+            self.transitivity(self.last_theorem().position, pos)?;
+        }
+
+        // Strip successors until one side is zero, then orient it as S..S0=0
+        let (smaller, larger) = if successor_count(&m) < successor_count(&n) {
+            (&m, &n)
+        } else {
+            (&n, &m)
+        };
+        for _ in 0..successor_count(smaller) {
+            self.predecessor(self.last_theorem().position)?;
+        }
+        if successor_count(&m) < successor_count(&n) {
+            self.symmetry(self.last_theorem().position)?;
+        }
+        self.implication()?;
+        self.contrapositive(self.last_theorem().position)?;
+        let implication = self.last_theorem().position;
+
+        // Axiom 0 states that the successor of a number is never zero
+        let diff = (0..successor_count(larger) - successor_count(smaller) - 1)
+            .fold(Term::zero(), |acc, _| Term::succ(&acc));
+        let ax = self.peano_axiom(0)?;
+        let nonzero = self.specify_all(ax, &[("a", &diff)])?;
+        self.detachment(nonzero, implication)?;
+        Ok(self.last_theorem().position)
+    }
+
+    // The right side of the equality in theorem n.
+    fn numeral_of(&self, n: usize) -> Term {
+        match self.theorem(n).formula() {
+            Formula::Equality(_, r) => r.clone(),
+            _ => unreachable!("evaluation always produces an equality"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::deduction::Rule;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_numeral_sum() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Sums");
+        let zero = &Term::zero();
+        let two = &Term::try_from("SS0").unwrap();
+        let n = d.numeral_sum(two, zero)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "(SS0+0)=SS0");
+        let n = d.numeral_sum(zero, two)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "(0+SS0)=SS0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeral_sum_reuse() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Sums");
+        let two = &Term::try_from("SS0").unwrap();
+        let three = &Term::try_from("SSS0").unwrap();
+        let n1 = d.numeral_sum(two, three)?;
+        let len = d.theorems().len();
+        let n2 = d.numeral_sum(two, three)?;
+        assert_eq!(n1, n2);
+        assert_eq!(len, d.theorems().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeral_product() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Products");
+        let zero = &Term::zero();
+        let three = &Term::try_from("SSS0").unwrap();
+        let n = d.numeral_product(three, three)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "(SSS0*SSS0)=SSSSSSSSS0");
+        let n = d.numeral_product(zero, three)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "(0*SSS0)=0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_inequality() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Inequalities");
+        let zero = &Term::zero();
+        let two = &Term::try_from("SS0").unwrap();
+        let three = &Term::try_from("SSS0").unwrap();
+        let n = d.inequality(three, two)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "~SSS0=SS0");
+        let n = d.inequality(zero, two)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "~0=SS0");
+        assert_eq!(d.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_inequality_closed() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Inequalities");
+        let s = &Term::try_from("(SS0*SS0)").unwrap();
+        let t = &Term::try_from("S(S0+SSS0)").unwrap();
+        let n = d.inequality(s, t)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "~(SS0*SS0)=S(S0+SSS0)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_inequality_err() {
+        let mut d = Deduction::new("Errors");
+        let s = &Term::try_from("(S0+S0)").unwrap();
+        let t = &Term::try_from("SS0").unwrap();
+        assert!(d.inequality(s, t).is_err());
+        assert!(d.inequality(&Term::var("a"), t).is_err());
+        assert!(d.inequality(t, &Term::var("a")).is_err());
+        // a failed tactic leaves nothing behind
+        assert_eq!(d.theorems().len(), 0);
+    }
+
+    #[test]
+    fn test_normalize() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Normalization");
+        let t = &Term::try_from("((S0+S0)*(S0+SS0))").unwrap();
+        let n = d.normalize(t)?;
+        assert_eq!(
+            d.theorem(n).formula().to_string(),
+            "((S0+S0)*(S0+SS0))=SSSSSS0"
+        );
+        let n = d.normalize(&Term::try_from("SS0").unwrap())?;
+        assert_eq!(d.theorem(n).formula().to_string(), "SS0=SS0");
+        assert_eq!(d.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_reuse() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Normalization");
+        let s = &Term::try_from("(SS0+(S0*S0))").unwrap();
+        let t = &Term::try_from("(S0+(SS0+S0))").unwrap();
+        d.normalize(s)?;
+        let len = d.theorems().len();
+        let n = d.normalize(s)?;
+        assert_eq!(len, d.theorems().len());
+        assert_eq!(d.theorem(n).formula().to_string(), "(SS0+(S0*S0))=SSS0");
+        // the congruence lemma for sums is already proven and only needs to be specified
+        let inductions =
+            |d: &Deduction| d.theorems().filter(|t| t.rule() == Rule::Induction).count();
+        let before = inductions(&d);
+        let n = d.normalize(t)?;
+        assert_eq!(d.theorem(n).formula().to_string(), "(S0+(SS0+S0))=SSSS0");
+        assert_eq!(before, inductions(&d));
+        Ok(())
+    }
+
+    #[test]
+    fn test_congruence() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Congruence");
+        d.supposition(Formula::try_from("(c+d)=d").unwrap())?;
+        let r = &Term::try_from("(c*d)").unwrap();
+        let n = d.sum_congruence_left(0, r)?;
+        assert_eq!(
+            d.theorem(n).formula().to_string(),
+            "((c+d)+(c*d))=(d+(c*d))"
+        );
+        let n = d.product_congruence_left(0, r)?;
+        assert_eq!(
+            d.theorem(n).formula().to_string(),
+            "((c+d)*(c*d))=(d*(c*d))"
+        );
+        let n = d.product_congruence_right(n, &Term::zero())?;
+        assert_eq!(
+            d.theorem(n).formula().to_string(),
+            "(0*((c+d)*(c*d)))=(0*(d*(c*d)))"
+        );
+        d.implication()?;
+        assert_eq!(d.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_congruence_err() {
+        let mut d = Deduction::new("Errors");
+        d.add_axiom(0).unwrap();
+        assert!(d.sum_congruence_left(0, &Term::zero()).is_err());
+    }
+
+    #[test]
+    fn test_numeral_err() {
+        let mut d = Deduction::new("Errors");
+        let a = &Term::var("a");
+        assert!(d.numeral_sum(a, &Term::one()).is_err());
+        let mut d = Deduction::custom("Errors", vec![]);
+        assert!(d.numeral_sum(&Term::one(), &Term::one()).is_err());
+    }
+}
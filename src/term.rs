@@ -1,3 +1,4 @@
+use crate::numbering::{ByteNumbering, GodelNumbering};
 use crate::parsing::parser::string_to_term;
 use crate::LogicError;
 use indexmap::IndexSet;
@@ -32,6 +33,11 @@ impl Term {
         Self::succ(&Self::Zero)
     }
 
+    /// The numeral for n, which is Term::Zero with n Term::Successor applied to it.
+    pub fn numeral(n: usize) -> Term {
+        (0..n).fold(Self::Zero, |acc, _| Self::succ(&acc))
+    }
+
     pub fn var<S: ToString>(name: S) -> Term {
         if VARIABLE_NAME.is_match(&name.to_string()) {
             Term::Variable(name.to_string())
@@ -53,18 +59,49 @@ impl Term {
         }
     }
 
+    /// Return a String formatting the Term in LaTeX.
     pub fn to_latex(&self) -> String {
         match self {
             Self::Zero => "0".into(),
             Self::Variable(v) => v.into(),
-            Self::Successor(inner) => format!("S{}", inner),
-            Self::Sum(lhs, rhs) => format!("({} + {})", lhs, rhs),
+            Self::Successor(inner) => format!("S{}", inner.to_latex()),
+            Self::Sum(lhs, rhs) => format!("({} + {})", lhs.to_latex(), rhs.to_latex()),
             Self::Product(lhs, rhs) => {
-                format!("({} \\cdot {})", lhs, rhs)
+                format!("({} \\cdot {})", lhs.to_latex(), rhs.to_latex())
             }
         }
     }
 
+    /// Return a String formatting the Term as MathML, without the enclosing math element.
+    pub fn to_mathml(&self) -> String {
+        match self {
+            Self::Zero => "<mn>0</mn>".into(),
+            Self::Variable(v) => {
+                let name = v.trim_end_matches('\'');
+                let primes = v.len() - name.len();
+                if primes == 0 {
+                    format!("<mi>{name}</mi>")
+                } else {
+                    format!(
+                        "<msup><mi>{name}</mi><mo>{}</mo></msup>",
+                        "′".repeat(primes)
+                    )
+                }
+            }
+            Self::Successor(inner) => format!("<mrow><mi>S</mi>{}</mrow>", inner.to_mathml()),
+            Self::Sum(lhs, rhs) => format!(
+                "<mrow><mo>(</mo>{}<mo>+</mo>{}<mo>)</mo></mrow>",
+                lhs.to_mathml(),
+                rhs.to_mathml()
+            ),
+            Self::Product(lhs, rhs) => format!(
+                "<mrow><mo>(</mo>{}<mo>·</mo>{}<mo>)</mo></mrow>",
+                lhs.to_mathml(),
+                rhs.to_mathml()
+            ),
+        }
+    }
+
     // Determine if a Term contains a Variable with a particular name
     pub fn contains_var<S: ToString>(&self, name: &S) -> bool {
         match self {
@@ -146,6 +183,57 @@ impl Term {
         }
     }
 
+    /// The Term found by following a path from this Term. Each step of the path picks a child: 0 for the inner Term of a Successor and 0 or 1 for the left or right side of a Sum or Product. The empty path is the Term itself.
+    /// ```
+    /// # use tnt::Term;
+    /// # use std::convert::TryFrom;
+    /// let t = Term::try_from("S(a+(b*c))").unwrap();
+    /// assert_eq!(t.subterm(&[0, 1, 0]), Some(&Term::var("b")));
+    /// assert_eq!(t.subterm(&[1]), None);
+    /// ```
+    pub fn subterm(&self, path: &[usize]) -> Option<&Term> {
+        match (self, path) {
+            (_, []) => Some(self),
+            (Self::Successor(inner), [0, rest @ ..]) => inner.subterm(rest),
+            (Self::Sum(lhs, _) | Self::Product(lhs, _), [0, rest @ ..]) => lhs.subterm(rest),
+            (Self::Sum(_, rhs) | Self::Product(_, rhs), [1, rest @ ..]) => rhs.subterm(rest),
+            _ => None,
+        }
+    }
+
+    // Mutable version of subterm.
+    pub(crate) fn subterm_mut(&mut self, path: &[usize]) -> Option<&mut Term> {
+        match (self, path) {
+            (t, []) => Some(t),
+            (Self::Successor(inner), [0, rest @ ..]) => inner.subterm_mut(rest),
+            (Self::Sum(lhs, _) | Self::Product(lhs, _), [0, rest @ ..]) => lhs.subterm_mut(rest),
+            (Self::Sum(_, rhs) | Self::Product(_, rhs), [1, rest @ ..]) => rhs.subterm_mut(rest),
+            _ => None,
+        }
+    }
+
+    // Push the path of every occurrence of the target, extending the given prefix.
+    pub(crate) fn paths_to(
+        &self,
+        target: &Term,
+        prefix: &mut Vec<usize>,
+        out: &mut Vec<Vec<usize>>,
+    ) {
+        if self == target {
+            out.push(prefix.clone());
+        }
+        let children: Vec<&Term> = match self {
+            Self::Zero | Self::Variable(_) => vec![],
+            Self::Successor(inner) => vec![inner],
+            Self::Sum(lhs, rhs) | Self::Product(lhs, rhs) => vec![lhs, rhs],
+        };
+        for (i, child) in children.into_iter().enumerate() {
+            prefix.push(i);
+            child.paths_to(target, prefix, out);
+            prefix.pop();
+        }
+    }
+
     /// Produces the Term in its austere form. The leftmost variable is renamed `a` in all appearances, the next is renamed `a'` and so on.
     pub fn austere(&self) -> Term {
         let mut out = self.clone();
@@ -154,6 +242,7 @@ impl Term {
     }
 
     /// Mutate the Term into its austere form. The leftmost variable is renamed `a` in all appearances, the next is renamed `a'` and so on.
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_austere(&mut self) {
         let vars = {
             let mut v = IndexSet::new();
@@ -164,6 +253,7 @@ impl Term {
     }
 
     // Produce an austere form with the variables renamed in a specific order. This is used only for creating an austere Deduction.
+    #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_austere_with(&mut self, vars: &IndexSet<String>) {
         let mut mask = String::from("#");
         for v in vars.iter() {
@@ -181,8 +271,35 @@ impl Term {
 
     /// Create the unique BigUint that characterizes the Term. This is done by converting the Term to its austere form and then reading the bytes as a bigendian number.
     pub fn arithmetize(&self) -> BigUint {
-        let s = self.clone().austere().to_string();
-        BigUint::from_bytes_be(s.as_bytes())
+        self.arithmetize_with(&ByteNumbering)
+            .expect("the byte numbering can encode any string")
+    }
+
+    /// Create the unique BigUint that characterizes the austere form of the Term in the given numbering, or an error if the numbering cannot encode it.
+    pub fn arithmetize_with(&self, numbering: &impl GodelNumbering) -> Result<BigUint, LogicError> {
+        numbering.encode(&self.austere().to_string())
+    }
+
+    /// Recover the austere Term characterized by a BigUint in the given numbering.
+    /// ```
+    /// # use tnt::Term;
+    /// # use tnt::numbering::CodonNumbering;
+    /// # use std::convert::TryFrom;
+    /// let t = Term::try_from("S(b+0)").unwrap();
+    /// let n = t.arithmetize_with(&CodonNumbering).unwrap();
+    /// assert_eq!(Term::from_arithmetized(&n, &CodonNumbering).unwrap(), t.austere());
+    /// ```
+    pub fn from_arithmetized(
+        number: &BigUint,
+        numbering: &impl GodelNumbering,
+    ) -> Result<Term, LogicError> {
+        let s = numbering.decode(number)?;
+        match Term::try_from(s.as_str()) {
+            Ok(t) if t.to_string() == s => Ok(t),
+            _ => Err(LogicError(format!(
+                "Numbering Error: `{s}` is not a well formed Term"
+            ))),
+        }
     }
 
     pub fn succ(term: &Term) -> Term {
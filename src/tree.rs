@@ -0,0 +1,231 @@
+//! Proof trees showing the lines that the conclusion of a Deduction depends on.
+//!
+//! Each Formula sits below the Formulas of the lines it was derived from. Axioms and suppositions are the leaves of the tree and a supposition is marked with the number of its line, which is also given for the implication that discharges it. Since an implication depends on its supposition only as a hypothesis the conclusion of the supposition is its only branch.
+//!
+//! A line used many times appears in full each time it is used unless the tree is shared. In a shared tree every line is derived once, the first time it is needed, and later uses refer back to it by line number. Trees that are not shared can be exponentially larger than the Deduction.
+//! ```
+//! # use tnt::{Deduction, Term};
+//! let mut d = Deduction::new("One Plus Zero");
+//! d.add_axiom(1).unwrap();
+//! d.specification(0, "a", &Term::one()).unwrap();
+//! d.successor(1).unwrap();
+//! assert_eq!(
+//!     d.ascii_tree(true),
+//!     "2) S(S0+0)=SS0 [successor]\n`-- 1) (S0+0)=S0 [specification]\n    `-- 0) Aa:(a+0)=a [axiom]\n"
+//! );
+//! ```
+
+use std::collections::HashSet;
+use std::io::{Error, Write};
+
+use crate::deduction::Justification;
+use crate::latex::begin_document;
+use crate::Deduction;
+
+// The lines a theorem is derived from in the tree.
+fn branches(justification: &Justification) -> Vec<usize> {
+    match justification {
+        Justification::Implication { conclusion, .. } => vec![*conclusion],
+        j => j.premises(),
+    }
+}
+
+// The name of the rule that produced a line as it is shown in the tree.
+fn rule_label(justification: &Justification) -> String {
+    match justification {
+        Justification::Implication { premise, .. } => format!("implication of {premise}"),
+        j => j.rule().to_string(),
+    }
+}
+
+impl Deduction {
+    /// A tree drawn in ASCII for the terminal with the conclusion of the Deduction as its root. When shared is true each line is expanded only once, as described in the module documentation.
+    pub fn ascii_tree(&self, shared: bool) -> String {
+        let mut out = String::new();
+        if let Some(last) = self.theorems().last() {
+            let mut seen = HashSet::new();
+            self.ascii_node(last.position, "", "", shared, &mut seen, &mut out);
+        }
+        out
+    }
+
+    // Write the line for theorem n followed by its branches, each line beginning with the prefix given for it.
+    fn ascii_node(
+        &self,
+        n: usize,
+        prefix: &str,
+        child_prefix: &str,
+        shared: bool,
+        seen: &mut HashSet<usize>,
+        out: &mut String,
+    ) {
+        let t = self.theorem(n);
+        let branches = branches(&t.justification);
+        if shared && !branches.is_empty() && !seen.insert(n) {
+            out.push_str(&format!("{prefix}{n}) {} [see above]\n", t.formula()));
+            return;
+        }
+        out.push_str(&format!(
+            "{prefix}{n}) {} [{}]\n",
+            t.formula(),
+            rule_label(&t.justification)
+        ));
+        for (i, b) in branches.iter().enumerate() {
+            let (p, c) = if i + 1 == branches.len() {
+                ("`-- ", "    ")
+            } else {
+                ("+-- ", "|   ")
+            };
+            self.ascii_node(
+                *b,
+                &format!("{child_prefix}{p}"),
+                &format!("{child_prefix}{c}"),
+                shared,
+                seen,
+                out,
+            );
+        }
+    }
+
+    /// A proof tree for the bussproofs LaTeX package with the conclusion of the Deduction as its root, given as a `prooftree` environment. When shared is true each line is derived only once, as described in the module documentation.
+    /// ```
+    /// # use tnt::{Deduction, Term};
+    /// let mut d = Deduction::new("One Plus Zero");
+    /// d.add_axiom(1).unwrap();
+    /// d.specification(0, "a", &Term::one()).unwrap();
+    /// assert_eq!(
+    ///     d.bussproofs(false),
+    ///     "\\begin{prooftree}\n\\AxiomC{$\\forall a: (a + 0) = a$}\n\\RightLabel{\\scriptsize specification (1)}\n\\UnaryInfC{$(S0 + 0) = S0$}\n\\end{prooftree}\n"
+    /// );
+    /// ```
+    pub fn bussproofs(&self, shared: bool) -> String {
+        let mut out = String::from("\\begin{prooftree}\n");
+        if let Some(last) = self.theorems().last() {
+            let mut seen = HashSet::new();
+            self.bussproofs_node(last.position, shared, &mut seen, &mut out);
+        }
+        out.push_str("\\end{prooftree}\n");
+        out
+    }
+
+    // Write the branches of theorem n followed by the inference that produces it, as bussproofs expects.
+    fn bussproofs_node(&self, n: usize, shared: bool, seen: &mut HashSet<usize>, out: &mut String) {
+        let t = self.theorem(n);
+        let formula = t.formula().to_latex();
+        let branches = branches(&t.justification);
+        if branches.is_empty() {
+            if t.justification == Justification::Supposition {
+                out.push_str(&format!("\\AxiomC{{$[{formula}]^{{{n}}}$}}\n"));
+            } else {
+                out.push_str(&format!("\\AxiomC{{${formula}$}}\n"));
+            }
+            return;
+        }
+        if shared && seen.contains(&n) {
+            out.push_str(&format!("\\AxiomC{{${formula}$ \\ \\textit{{({n})}}}}\n"));
+            return;
+        }
+        for b in branches.iter() {
+            self.bussproofs_node(*b, shared, seen, out);
+        }
+        seen.insert(n);
+        let inference = match branches.len() {
+            1 => "UnaryInfC",
+            2 => "BinaryInfC",
+            _ => "TrinaryInfC",
+        };
+        let label = match &t.justification {
+            Justification::Implication { premise, .. } => format!("implication$^{{{premise}}}$"),
+            j => j.rule().to_string(),
+        };
+        out.push_str(&format!("\\RightLabel{{\\scriptsize {label} ({n})}}\n"));
+        out.push_str(&format!("\\{inference}{{${formula}$}}\n"));
+    }
+
+    /// Write a LaTeX document holding the proof tree given by bussproofs.
+    pub fn write_bussproofs(&self, out: &mut impl Write, shared: bool) -> Result<(), Error> {
+        begin_document(out, "bussproofs", &self.title)?;
+        write!(out, "{}", self.bussproofs(shared))?;
+        writeln!(out, "\\end{{document}}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{Formula, LogicError};
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_ascii_tree() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Fantasy");
+        d.add_axiom(1)?;
+        d.supposition(Formula::try_from("a=0")?)?;
+        d.symmetry(1)?;
+        d.transitivity(1, 2)?;
+        d.transitivity(3, 1)?;
+        d.implication()?;
+        let tree = d.ascii_tree(true);
+        let expected = "\
+5) [a=0>a=0] [implication of 1]
+`-- 4) a=0 [transitivity]
+    +-- 3) a=a [transitivity]
+    |   +-- 1) a=0 [supposition]
+    |   `-- 2) 0=a [symmetry]
+    |       `-- 1) a=0 [supposition]
+    `-- 1) a=0 [supposition]
+";
+        assert_eq!(tree, expected);
+        // the axiom was never used
+        assert!(!tree.contains("Aa:(a+0)=a"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_tree() -> Result<(), LogicError> {
+        let mut d = Deduction::new("Shared");
+        d.add_axiom(1)?;
+        d.specification(0, "a", &crate::Term::zero())?;
+        d.symmetry(1)?;
+        d.transitivity(1, 2)?;
+        d.transitivity(3, 3)?;
+        let expected = "\
+4) (0+0)=(0+0) [transitivity]
++-- 3) (0+0)=(0+0) [transitivity]
+|   +-- 1) (0+0)=0 [specification]
+|   |   `-- 0) Aa:(a+0)=a [axiom]
+|   `-- 2) 0=(0+0) [symmetry]
+|       `-- 1) (0+0)=0 [see above]
+`-- 3) (0+0)=(0+0) [see above]
+";
+        assert_eq!(d.ascii_tree(true), expected);
+        let unshared = d.ascii_tree(false);
+        assert!(!unshared.contains("see above"));
+        assert_eq!(unshared.lines().count(), 13);
+
+        let expected = "\
+\\begin{prooftree}
+\\AxiomC{$\\forall a: (a + 0) = a$}
+\\RightLabel{\\scriptsize specification (1)}
+\\UnaryInfC{$(0 + 0) = 0$}
+\\AxiomC{$(0 + 0) = 0$ \\ \\textit{(1)}}
+\\RightLabel{\\scriptsize symmetry (2)}
+\\UnaryInfC{$0 = (0 + 0)$}
+\\RightLabel{\\scriptsize transitivity (3)}
+\\BinaryInfC{$(0 + 0) = (0 + 0)$}
+\\AxiomC{$(0 + 0) = (0 + 0)$ \\ \\textit{(3)}}
+\\RightLabel{\\scriptsize transitivity (4)}
+\\BinaryInfC{$(0 + 0) = (0 + 0)$}
+\\end{prooftree}
+";
+        assert_eq!(d.bussproofs(true), expected);
+        assert_eq!(d.bussproofs(false).matches("\\BinaryInfC").count(), 3);
+        let mut out = Vec::new();
+        d.write_bussproofs(&mut out, true).unwrap();
+        let tex = String::from_utf8(out).unwrap();
+        assert!(tex.contains("\\usepackage{bussproofs}"));
+        assert!(tex.ends_with(&format!("{expected}\\end{{document}}\n")));
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+//! The scripts in the examples directory hold the Deductions that the examples of the same name build.
+
+use tnt::{Deduction, LogicError};
+
+#[allow(dead_code)]
+#[path = "../examples/addition.rs"]
+mod addition;
+#[allow(dead_code)]
+#[path = "../examples/commutativity.rs"]
+mod commutativity;
+
+fn assert_same(script: &str, built: &Deduction) -> Result<(), LogicError> {
+    let read = Deduction::from_script(script)?;
+    assert_eq!(read.title, built.title);
+    assert_eq!(read.axioms(), built.axioms());
+    assert_eq!(read.theorems().len(), built.theorems().len());
+    for (t1, t2) in read.theorems().zip(built.theorems()) {
+        assert_eq!(t1.formula(), t2.formula());
+        assert_eq!(t1.justification(), t2.justification());
+        assert_eq!((t1.depth(), t1.scope()), (t2.depth(), t2.scope()));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_addition_script() -> Result<(), LogicError> {
+    assert_same(
+        include_str!("../examples/addition.tnt"),
+        &addition::deduction()?,
+    )
+}
+
+#[test]
+fn test_commutativity_script() -> Result<(), LogicError> {
+    assert_same(
+        include_str!("../examples/commutativity.tnt"),
+        &commutativity::deduction()?,
+    )
+}